@@ -0,0 +1,58 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+/// A wordnik api key.
+///
+/// `Debug` and `Display` print `<api_key>` rather than the key itself, so that clients, builders
+/// and api responses can be logged without leaking it. Use `expose` to get at the key.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for ApiKey {
+    fn from(key: String) -> Self {
+        Self(key)
+    }
+}
+
+impl From<&str> for ApiKey {
+    fn from(key: &str) -> Self {
+        Self(key.into())
+    }
+}
+
+impl fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<api_key>")
+    }
+}
+
+impl fmt::Display for ApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<api_key>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ApiKey;
+
+    #[test]
+    fn api_key_is_redacted() {
+        let key = ApiKey::new("hunter2");
+        assert_eq!(key.expose(), "hunter2");
+        assert_eq!(key.to_string(), "<api_key>");
+        assert!(!format!("{:?}", Some(&key)).contains("hunter2"));
+    }
+}
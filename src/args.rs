@@ -1,8 +1,32 @@
+mod audio;
 mod definitions;
+mod examples;
+mod frequency;
+mod hyphenation;
+mod phrases;
+mod pronunciations;
 mod random;
+mod related;
+mod reverse;
+#[cfg(feature = "deprecated-endpoints")]
+mod search;
+mod word_list;
 
+pub use audio::AudioArgs;
 pub use definitions::DefinitionsArgs;
+pub use examples::ExamplesArgs;
+pub use frequency::FrequencyArgs;
+pub use hyphenation::HyphenationArgs;
+pub use phrases::PhrasesArgs;
+pub use pronunciations::PronunciationsArgs;
 pub use random::{RandomWordArgs, RandomWordsArgs};
+pub use related::RelatedWordsArgs;
+pub use reverse::ReverseDictionaryArgs;
+#[cfg(feature = "deprecated-endpoints")]
+pub use search::SearchArgs;
+pub use word_list::WordListWordsArgs;
+
+use crate::encode;
 
 pub trait Args<'a> {
     type KeyValuePairs: Iterator<Item = (&'static str, String)> + 'a;
@@ -12,7 +36,7 @@ pub trait Args<'a> {
     fn to_get_query_str(&'a self) -> String {
         let mut args = self.args();
         let mut buf = match args.next() {
-            Some((key, value)) => format!("{}={}", key, value),
+            Some((key, value)) => format!("{}={}", key, encode::component(&value)),
             None => return String::new(),
         };
 
@@ -20,7 +44,7 @@ pub trait Args<'a> {
             buf += "&";
             buf += key;
             buf += "=";
-            buf += &value;
+            buf += &encode::component(&value).to_string();
         });
 
         buf
@@ -59,6 +83,37 @@ impl StringParam for SortOrder {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub enum ExpandTerms {
+    Synonym,
+    Hypernym,
+}
+
+impl StringParam for ExpandTerms {
+    fn as_str(self) -> &'static str {
+        match self {
+            ExpandTerms::Synonym => "synonym",
+            ExpandTerms::Hypernym => "hypernym",
+        }
+    }
+}
+
+// Word lists sort on different things than everything else.
+#[derive(Copy, Clone, Debug)]
+pub enum WordListSortType {
+    Alpha,
+    CreateDate,
+}
+
+impl StringParam for WordListSortType {
+    fn as_str(self) -> &'static str {
+        match self {
+            WordListSortType::Alpha => "alpha",
+            WordListSortType::CreateDate => "createDate",
+        }
+    }
+}
+
 trait StringParam: Copy {
     fn as_str(self) -> &'static str;
 }
@@ -128,6 +183,80 @@ impl StringParam for PartOfSpeech {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RelationshipType {
+    Antonym,
+    CrossReference, // cross-reference
+    Equivalent,
+    EtymologicallyRelatedTerm, // etymologically-related-term
+    Form,
+    HasTopic, // has_topic
+    Hypernym,
+    Hyponym,
+    InflectedForm, // inflected-form
+    Primary,
+    RelatedWord, // related-word
+    Rhyme,
+    SameContext, // same-context
+    Synonym,
+    Unknown,
+    Variant,
+    VerbForm, // verb-form
+    VerbStem, // verb-stem
+}
+
+impl RelationshipType {
+    // Anything we don't recognize gets lumped in with "unknown" rather than failing the whole
+    // response.
+    pub(crate) fn from_str_lossy(s: &str) -> Self {
+        match s {
+            "antonym" => RelationshipType::Antonym,
+            "cross-reference" => RelationshipType::CrossReference,
+            "equivalent" => RelationshipType::Equivalent,
+            "etymologically-related-term" => RelationshipType::EtymologicallyRelatedTerm,
+            "form" => RelationshipType::Form,
+            "has_topic" => RelationshipType::HasTopic,
+            "hypernym" => RelationshipType::Hypernym,
+            "hyponym" => RelationshipType::Hyponym,
+            "inflected-form" => RelationshipType::InflectedForm,
+            "primary" => RelationshipType::Primary,
+            "related-word" => RelationshipType::RelatedWord,
+            "rhyme" => RelationshipType::Rhyme,
+            "same-context" => RelationshipType::SameContext,
+            "synonym" => RelationshipType::Synonym,
+            "variant" => RelationshipType::Variant,
+            "verb-form" => RelationshipType::VerbForm,
+            "verb-stem" => RelationshipType::VerbStem,
+            _ => RelationshipType::Unknown,
+        }
+    }
+}
+
+impl StringParam for RelationshipType {
+    fn as_str(self) -> &'static str {
+        match self {
+            RelationshipType::Antonym => "antonym",
+            RelationshipType::CrossReference => "cross-reference",
+            RelationshipType::Equivalent => "equivalent",
+            RelationshipType::EtymologicallyRelatedTerm => "etymologically-related-term",
+            RelationshipType::Form => "form",
+            RelationshipType::HasTopic => "has_topic",
+            RelationshipType::Hypernym => "hypernym",
+            RelationshipType::Hyponym => "hyponym",
+            RelationshipType::InflectedForm => "inflected-form",
+            RelationshipType::Primary => "primary",
+            RelationshipType::RelatedWord => "related-word",
+            RelationshipType::Rhyme => "rhyme",
+            RelationshipType::SameContext => "same-context",
+            RelationshipType::Synonym => "synonym",
+            RelationshipType::Unknown => "unknown",
+            RelationshipType::Variant => "variant",
+            RelationshipType::VerbForm => "verb-form",
+            RelationshipType::VerbStem => "verb-stem",
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum SourceDictionaries {
     All,
@@ -155,6 +284,25 @@ impl StringParam for SourceDictionaries {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub enum TypeFormat {
+    AmericanHeritage, // ahd-5
+    Arpabet,
+    GcideDiacritical, // gcide-diacritical
+    Ipa,              // IPA
+}
+
+impl StringParam for TypeFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            TypeFormat::AmericanHeritage => "ahd-5",
+            TypeFormat::Arpabet => "arpabet",
+            TypeFormat::GcideDiacritical => "gcide-diacritical",
+            TypeFormat::Ipa => "IPA",
+        }
+    }
+}
+
 fn format_csv(params: &[impl StringParam]) -> String {
     let mut params = params.iter();
     let mut buf = match params.next() {
@@ -171,7 +319,7 @@ fn format_csv(params: &[impl StringParam]) -> String {
 }
 
 fn format_enum(param: &impl StringParam) -> String {
-    return param.as_str().to_string();
+    param.as_str().to_string()
 }
 
 fn format_bool(param: bool) -> String {
@@ -0,0 +1,60 @@
+use super::{format_bool, Args};
+
+pub struct AudioArgs {
+    pub use_canonical: bool,
+    pub limit: u32,
+}
+
+impl AudioArgs {
+    const DEFAULT_LIMIT: u32 = 50;
+
+    pub fn new() -> Self {
+        Self {
+            use_canonical: false,
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+}
+
+impl Default for AudioArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct AudioArgsIter<'a> {
+    args: &'a AudioArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for AudioArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                0 => {
+                    self.idx += 1;
+                    if self.args.use_canonical {
+                        return Some(("useCanonical", format_bool(self.args.use_canonical)));
+                    }
+                }
+                1 => {
+                    self.idx += 1;
+                    if self.args.limit != AudioArgs::DEFAULT_LIMIT {
+                        return Some(("limit", self.args.limit.to_string()));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for AudioArgs {
+    type KeyValuePairs = AudioArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        AudioArgsIter { args: self, idx: 0 }
+    }
+}
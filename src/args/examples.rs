@@ -0,0 +1,81 @@
+use super::{format_bool, Args};
+
+#[derive(Clone, Debug)]
+pub struct ExamplesArgs {
+    pub include_duplicates: bool,
+    pub use_canonical: bool,
+    pub skip: u32,
+    pub limit: u32,
+}
+
+impl ExamplesArgs {
+    const DEFAULT_SKIP: u32 = 0;
+    const DEFAULT_LIMIT: u32 = 5;
+
+    pub fn new() -> Self {
+        Self {
+            include_duplicates: false,
+            use_canonical: false,
+            skip: Self::DEFAULT_SKIP,
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+}
+
+impl Default for ExamplesArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct ExamplesArgsIter<'a> {
+    args: &'a ExamplesArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for ExamplesArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                0 => {
+                    self.idx += 1;
+                    if self.args.include_duplicates {
+                        return Some((
+                            "includeDuplicates",
+                            format_bool(self.args.include_duplicates),
+                        ));
+                    }
+                }
+                1 => {
+                    self.idx += 1;
+                    if self.args.use_canonical {
+                        return Some(("useCanonical", format_bool(self.args.use_canonical)));
+                    }
+                }
+                2 => {
+                    self.idx += 1;
+                    if self.args.skip != ExamplesArgs::DEFAULT_SKIP {
+                        return Some(("skip", self.args.skip.to_string()));
+                    }
+                }
+                3 => {
+                    self.idx += 1;
+                    if self.args.limit != ExamplesArgs::DEFAULT_LIMIT {
+                        return Some(("limit", self.args.limit.to_string()));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for ExamplesArgs {
+    type KeyValuePairs = ExamplesArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        ExamplesArgsIter { args: self, idx: 0 }
+    }
+}
@@ -0,0 +1,69 @@
+use super::{format_bool, Args};
+
+pub struct FrequencyArgs {
+    pub use_canonical: bool,
+    pub start_year: u32,
+    pub end_year: u32,
+}
+
+impl FrequencyArgs {
+    const DEFAULT_START_YEAR: u32 = 1800;
+    const DEFAULT_END_YEAR: u32 = 2012;
+
+    pub fn new() -> Self {
+        Self {
+            use_canonical: false,
+            start_year: Self::DEFAULT_START_YEAR,
+            end_year: Self::DEFAULT_END_YEAR,
+        }
+    }
+}
+
+impl Default for FrequencyArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct FrequencyArgsIter<'a> {
+    args: &'a FrequencyArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for FrequencyArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                0 => {
+                    self.idx += 1;
+                    if self.args.use_canonical {
+                        return Some(("useCanonical", format_bool(self.args.use_canonical)));
+                    }
+                }
+                1 => {
+                    self.idx += 1;
+                    if self.args.start_year != FrequencyArgs::DEFAULT_START_YEAR {
+                        return Some(("startYear", self.args.start_year.to_string()));
+                    }
+                }
+                2 => {
+                    self.idx += 1;
+                    if self.args.end_year != FrequencyArgs::DEFAULT_END_YEAR {
+                        return Some(("endYear", self.args.end_year.to_string()));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for FrequencyArgs {
+    type KeyValuePairs = FrequencyArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        FrequencyArgsIter { args: self, idx: 0 }
+    }
+}
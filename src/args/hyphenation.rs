@@ -0,0 +1,68 @@
+use super::{format_bool, format_enum, Args, SourceDictionaries};
+
+pub struct HyphenationArgs {
+    pub use_canonical: bool,
+    pub source_dictionary: Option<SourceDictionaries>,
+    pub limit: u32,
+}
+
+impl HyphenationArgs {
+    const DEFAULT_LIMIT: u32 = 50;
+
+    pub fn new() -> Self {
+        Self {
+            use_canonical: false,
+            source_dictionary: None,
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+}
+
+impl Default for HyphenationArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct HyphenationArgsIter<'a> {
+    args: &'a HyphenationArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for HyphenationArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                0 => {
+                    self.idx += 1;
+                    if self.args.use_canonical {
+                        return Some(("useCanonical", format_bool(self.args.use_canonical)));
+                    }
+                }
+                1 => {
+                    self.idx += 1;
+                    if let Some(source_dictionary) = self.args.source_dictionary {
+                        return Some(("sourceDictionary", format_enum(&source_dictionary)));
+                    }
+                }
+                2 => {
+                    self.idx += 1;
+                    if self.args.limit != HyphenationArgs::DEFAULT_LIMIT {
+                        return Some(("limit", self.args.limit.to_string()));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for HyphenationArgs {
+    type KeyValuePairs = HyphenationArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        HyphenationArgsIter { args: self, idx: 0 }
+    }
+}
@@ -0,0 +1,69 @@
+use super::{format_bool, Args};
+
+pub struct PhrasesArgs {
+    pub limit: u32,
+    pub wlmi: u32,
+    pub use_canonical: bool,
+}
+
+impl PhrasesArgs {
+    const DEFAULT_LIMIT: u32 = 5;
+    const DEFAULT_WLMI: u32 = 0;
+
+    pub fn new() -> Self {
+        Self {
+            limit: Self::DEFAULT_LIMIT,
+            wlmi: Self::DEFAULT_WLMI,
+            use_canonical: false,
+        }
+    }
+}
+
+impl Default for PhrasesArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct PhrasesArgsIter<'a> {
+    args: &'a PhrasesArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for PhrasesArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                0 => {
+                    self.idx += 1;
+                    if self.args.limit != PhrasesArgs::DEFAULT_LIMIT {
+                        return Some(("limit", self.args.limit.to_string()));
+                    }
+                }
+                1 => {
+                    self.idx += 1;
+                    if self.args.wlmi != PhrasesArgs::DEFAULT_WLMI {
+                        return Some(("wlmi", self.args.wlmi.to_string()));
+                    }
+                }
+                2 => {
+                    self.idx += 1;
+                    if self.args.use_canonical {
+                        return Some(("useCanonical", format_bool(self.args.use_canonical)));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for PhrasesArgs {
+    type KeyValuePairs = PhrasesArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        PhrasesArgsIter { args: self, idx: 0 }
+    }
+}
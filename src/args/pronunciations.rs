@@ -0,0 +1,76 @@
+use super::{format_bool, format_enum, Args, SourceDictionaries, TypeFormat};
+
+pub struct PronunciationsArgs {
+    pub use_canonical: bool,
+    pub source_dictionary: Option<SourceDictionaries>,
+    pub type_format: Option<TypeFormat>,
+    pub limit: u32,
+}
+
+impl PronunciationsArgs {
+    const DEFAULT_LIMIT: u32 = 50;
+
+    pub fn new() -> Self {
+        Self {
+            use_canonical: false,
+            source_dictionary: None,
+            type_format: None,
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+}
+
+impl Default for PronunciationsArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct PronunciationsArgsIter<'a> {
+    args: &'a PronunciationsArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for PronunciationsArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                0 => {
+                    self.idx += 1;
+                    if self.args.use_canonical {
+                        return Some(("useCanonical", format_bool(self.args.use_canonical)));
+                    }
+                }
+                1 => {
+                    self.idx += 1;
+                    if let Some(source_dictionary) = self.args.source_dictionary {
+                        return Some(("sourceDictionary", format_enum(&source_dictionary)));
+                    }
+                }
+                2 => {
+                    self.idx += 1;
+                    if let Some(type_format) = self.args.type_format {
+                        return Some(("typeFormat", format_enum(&type_format)));
+                    }
+                }
+                3 => {
+                    self.idx += 1;
+                    if self.args.limit != PronunciationsArgs::DEFAULT_LIMIT {
+                        return Some(("limit", self.args.limit.to_string()));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for PronunciationsArgs {
+    type KeyValuePairs = PronunciationsArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        PronunciationsArgsIter { args: self, idx: 0 }
+    }
+}
@@ -78,11 +78,8 @@ impl<'a> Iterator for RandomWordArgsIter<'a> {
                 }
                 3 => {
                     self.idx += 1;
-                    if self.args.min_corpus_count.is_some() {
-                        return Some((
-                            "minCorpusCount",
-                            self.args.min_corpus_count.unwrap().to_string(),
-                        ));
+                    if let Some(min_corpus_count) = self.args.min_corpus_count {
+                        return Some(("minCorpusCount", min_corpus_count.to_string()));
                     }
                 }
                 4 => {
@@ -223,11 +220,8 @@ impl<'a> Iterator for RandomWordsArgsIter<'a> {
                 }
                 3 => {
                     self.idx += 1;
-                    if self.args.min_corpus_count.is_some() {
-                        return Some((
-                            "minCorpusCount",
-                            self.args.min_corpus_count.unwrap().to_string(),
-                        ));
+                    if let Some(min_corpus_count) = self.args.min_corpus_count {
+                        return Some(("minCorpusCount", min_corpus_count.to_string()));
                     }
                 }
                 4 => {
@@ -268,14 +262,14 @@ impl<'a> Iterator for RandomWordsArgsIter<'a> {
                 }
                 9 => {
                     self.idx += 1;
-                    if self.args.sort_by.is_some() {
-                        return Some(("sortBy", format_enum(&self.args.sort_by.unwrap())));
+                    if let Some(sort_by) = self.args.sort_by {
+                        return Some(("sortBy", format_enum(&sort_by)));
                     }
                 }
                 10 => {
                     self.idx += 1;
-                    if self.args.sort_order.is_some() {
-                        return Some(("sortOrder", format_enum(&self.args.sort_order.unwrap())));
+                    if let Some(sort_order) = self.args.sort_order {
+                        return Some(("sortOrder", format_enum(&sort_order)));
                     }
                 }
                 11 => {
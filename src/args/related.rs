@@ -0,0 +1,76 @@
+use super::{format_bool, format_csv, Args, RelationshipType};
+
+pub struct RelatedWordsArgs {
+    pub use_canonical: bool,
+    pub relationship_types: Vec<RelationshipType>,
+    pub limit_per_relationship_type: u32,
+}
+
+impl RelatedWordsArgs {
+    const DEFAULT_LIMIT_PER_RELATIONSHIP_TYPE: u32 = 10;
+
+    pub fn new() -> Self {
+        Self {
+            use_canonical: false,
+            relationship_types: Vec::new(),
+            limit_per_relationship_type: Self::DEFAULT_LIMIT_PER_RELATIONSHIP_TYPE,
+        }
+    }
+}
+
+impl Default for RelatedWordsArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct RelatedWordsArgsIter<'a> {
+    args: &'a RelatedWordsArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for RelatedWordsArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                0 => {
+                    self.idx += 1;
+                    if self.args.use_canonical {
+                        return Some(("useCanonical", format_bool(self.args.use_canonical)));
+                    }
+                }
+                1 => {
+                    self.idx += 1;
+                    if !self.args.relationship_types.is_empty() {
+                        return Some((
+                            "relationshipTypes",
+                            format_csv(&self.args.relationship_types),
+                        ));
+                    }
+                }
+                2 => {
+                    self.idx += 1;
+                    if self.args.limit_per_relationship_type
+                        != RelatedWordsArgs::DEFAULT_LIMIT_PER_RELATIONSHIP_TYPE
+                    {
+                        return Some((
+                            "limitPerRelationshipType",
+                            self.args.limit_per_relationship_type.to_string(),
+                        ));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for RelatedWordsArgs {
+    type KeyValuePairs = RelatedWordsArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        RelatedWordsArgsIter { args: self, idx: 0 }
+    }
+}
@@ -0,0 +1,185 @@
+use super::{
+    format_bool, format_csv, format_enum, Args, ExpandTerms, PartOfSpeech, SortOrder, SortType,
+    SourceDictionaries,
+};
+
+pub struct ReverseDictionaryArgs {
+    pub query: String,
+    pub find_sense_for_word: Option<String>,
+    pub include_source_dictionaries: Vec<SourceDictionaries>,
+    pub exclude_source_dictionaries: Vec<SourceDictionaries>,
+    pub include_part_of_speech: Vec<PartOfSpeech>,
+    pub exclude_part_of_speech: Vec<PartOfSpeech>,
+    pub min_corpus_count: u32,
+    pub max_corpus_count: i32,
+    pub min_length: u32,
+    pub max_length: i32,
+    pub expand_terms: Option<ExpandTerms>,
+    pub include_tags: bool,
+    pub sort_by: Option<SortType>,
+    pub sort_order: Option<SortOrder>,
+    pub skip: u32,
+    pub limit: u32,
+}
+
+impl ReverseDictionaryArgs {
+    const DEFAULT_MIN_CORPUS_COUNT: u32 = 5;
+    const DEFAULT_MAX: i32 = -1;
+    const DEFAULT_MIN_LENGTH: u32 = 1;
+    const DEFAULT_SKIP: u32 = 0;
+    const DEFAULT_LIMIT: u32 = 10;
+
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            find_sense_for_word: None,
+            include_source_dictionaries: Vec::new(),
+            exclude_source_dictionaries: Vec::new(),
+            include_part_of_speech: Vec::new(),
+            exclude_part_of_speech: Vec::new(),
+            min_corpus_count: Self::DEFAULT_MIN_CORPUS_COUNT,
+            max_corpus_count: Self::DEFAULT_MAX,
+            min_length: Self::DEFAULT_MIN_LENGTH,
+            max_length: Self::DEFAULT_MAX,
+            expand_terms: None,
+            include_tags: false,
+            sort_by: None,
+            sort_order: None,
+            skip: Self::DEFAULT_SKIP,
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+}
+
+pub struct ReverseDictionaryArgsIter<'a> {
+    args: &'a ReverseDictionaryArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for ReverseDictionaryArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                // The query is the one required parameter, so it always goes out.
+                0 => {
+                    self.idx += 1;
+                    return Some(("query", self.args.query.clone()));
+                }
+                1 => {
+                    self.idx += 1;
+                    if let Some(word) = &self.args.find_sense_for_word {
+                        return Some(("findSenseForWord", word.clone()));
+                    }
+                }
+                2 => {
+                    self.idx += 1;
+                    if !self.args.include_source_dictionaries.is_empty() {
+                        return Some((
+                            "includeSourceDictionaries",
+                            format_csv(&self.args.include_source_dictionaries),
+                        ));
+                    }
+                }
+                3 => {
+                    self.idx += 1;
+                    if !self.args.exclude_source_dictionaries.is_empty() {
+                        return Some((
+                            "excludeSourceDictionaries",
+                            format_csv(&self.args.exclude_source_dictionaries),
+                        ));
+                    }
+                }
+                4 => {
+                    self.idx += 1;
+                    if !self.args.include_part_of_speech.is_empty() {
+                        return Some((
+                            "includePartOfSpeech",
+                            format_csv(&self.args.include_part_of_speech),
+                        ));
+                    }
+                }
+                5 => {
+                    self.idx += 1;
+                    if !self.args.exclude_part_of_speech.is_empty() {
+                        return Some((
+                            "excludePartOfSpeech",
+                            format_csv(&self.args.exclude_part_of_speech),
+                        ));
+                    }
+                }
+                6 => {
+                    self.idx += 1;
+                    if self.args.min_corpus_count != ReverseDictionaryArgs::DEFAULT_MIN_CORPUS_COUNT
+                    {
+                        return Some(("minCorpusCount", self.args.min_corpus_count.to_string()));
+                    }
+                }
+                7 => {
+                    self.idx += 1;
+                    if self.args.max_corpus_count != ReverseDictionaryArgs::DEFAULT_MAX {
+                        return Some(("maxCorpusCount", self.args.max_corpus_count.to_string()));
+                    }
+                }
+                8 => {
+                    self.idx += 1;
+                    if self.args.min_length != ReverseDictionaryArgs::DEFAULT_MIN_LENGTH {
+                        return Some(("minLength", self.args.min_length.to_string()));
+                    }
+                }
+                9 => {
+                    self.idx += 1;
+                    if self.args.max_length != ReverseDictionaryArgs::DEFAULT_MAX {
+                        return Some(("maxLength", self.args.max_length.to_string()));
+                    }
+                }
+                10 => {
+                    self.idx += 1;
+                    if let Some(expand_terms) = self.args.expand_terms {
+                        return Some(("expandTerms", format_enum(&expand_terms)));
+                    }
+                }
+                11 => {
+                    self.idx += 1;
+                    if self.args.include_tags {
+                        return Some(("includeTags", format_bool(self.args.include_tags)));
+                    }
+                }
+                12 => {
+                    self.idx += 1;
+                    if let Some(sort_by) = self.args.sort_by {
+                        return Some(("sortBy", format_enum(&sort_by)));
+                    }
+                }
+                13 => {
+                    self.idx += 1;
+                    if let Some(sort_order) = self.args.sort_order {
+                        return Some(("sortOrder", format_enum(&sort_order)));
+                    }
+                }
+                14 => {
+                    self.idx += 1;
+                    if self.args.skip != ReverseDictionaryArgs::DEFAULT_SKIP {
+                        return Some(("skip", self.args.skip.to_string()));
+                    }
+                }
+                15 => {
+                    self.idx += 1;
+                    if self.args.limit != ReverseDictionaryArgs::DEFAULT_LIMIT {
+                        return Some(("limit", self.args.limit.to_string()));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for ReverseDictionaryArgs {
+    type KeyValuePairs = ReverseDictionaryArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        ReverseDictionaryArgsIter { args: self, idx: 0 }
+    }
+}
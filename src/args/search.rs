@@ -0,0 +1,149 @@
+use super::{format_bool, format_csv, Args, PartOfSpeech};
+
+pub struct SearchArgs {
+    pub case_sensitive: bool,
+    pub include_part_of_speech: Vec<PartOfSpeech>,
+    pub exclude_part_of_speech: Vec<PartOfSpeech>,
+    pub min_corpus_count: u32,
+    pub max_corpus_count: i32,
+    pub min_dictionary_count: u32,
+    pub max_dictionary_count: i32,
+    pub min_length: u32,
+    pub max_length: i32,
+    pub skip: u32,
+    pub limit: u32,
+}
+
+impl SearchArgs {
+    const DEFAULT_MIN_CORPUS_COUNT: u32 = 5;
+    const DEFAULT_MIN_DICTIONARY_COUNT: u32 = 1;
+    const DEFAULT_MAX: i32 = -1;
+    const DEFAULT_MIN_LENGTH: u32 = 1;
+    const DEFAULT_SKIP: u32 = 0;
+    const DEFAULT_LIMIT: u32 = 10;
+
+    pub fn new() -> Self {
+        Self {
+            case_sensitive: true,
+            include_part_of_speech: Vec::new(),
+            exclude_part_of_speech: Vec::new(),
+            min_corpus_count: Self::DEFAULT_MIN_CORPUS_COUNT,
+            max_corpus_count: Self::DEFAULT_MAX,
+            min_dictionary_count: Self::DEFAULT_MIN_DICTIONARY_COUNT,
+            max_dictionary_count: Self::DEFAULT_MAX,
+            min_length: Self::DEFAULT_MIN_LENGTH,
+            max_length: Self::DEFAULT_MAX,
+            skip: Self::DEFAULT_SKIP,
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+}
+
+impl Default for SearchArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct SearchArgsIter<'a> {
+    args: &'a SearchArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for SearchArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                0 => {
+                    self.idx += 1;
+                    if !self.args.case_sensitive {
+                        return Some(("caseSensitive", format_bool(self.args.case_sensitive)));
+                    }
+                }
+                1 => {
+                    self.idx += 1;
+                    if !self.args.include_part_of_speech.is_empty() {
+                        return Some((
+                            "includePartOfSpeech",
+                            format_csv(&self.args.include_part_of_speech),
+                        ));
+                    }
+                }
+                2 => {
+                    self.idx += 1;
+                    if !self.args.exclude_part_of_speech.is_empty() {
+                        return Some((
+                            "excludePartOfSpeech",
+                            format_csv(&self.args.exclude_part_of_speech),
+                        ));
+                    }
+                }
+                3 => {
+                    self.idx += 1;
+                    if self.args.min_corpus_count != SearchArgs::DEFAULT_MIN_CORPUS_COUNT {
+                        return Some(("minCorpusCount", self.args.min_corpus_count.to_string()));
+                    }
+                }
+                4 => {
+                    self.idx += 1;
+                    if self.args.max_corpus_count != SearchArgs::DEFAULT_MAX {
+                        return Some(("maxCorpusCount", self.args.max_corpus_count.to_string()));
+                    }
+                }
+                5 => {
+                    self.idx += 1;
+                    if self.args.min_dictionary_count != SearchArgs::DEFAULT_MIN_DICTIONARY_COUNT {
+                        return Some((
+                            "minDictionaryCount",
+                            self.args.min_dictionary_count.to_string(),
+                        ));
+                    }
+                }
+                6 => {
+                    self.idx += 1;
+                    if self.args.max_dictionary_count != SearchArgs::DEFAULT_MAX {
+                        return Some((
+                            "maxDictionaryCount",
+                            self.args.max_dictionary_count.to_string(),
+                        ));
+                    }
+                }
+                7 => {
+                    self.idx += 1;
+                    if self.args.min_length != SearchArgs::DEFAULT_MIN_LENGTH {
+                        return Some(("minLength", self.args.min_length.to_string()));
+                    }
+                }
+                8 => {
+                    self.idx += 1;
+                    if self.args.max_length != SearchArgs::DEFAULT_MAX {
+                        return Some(("maxLength", self.args.max_length.to_string()));
+                    }
+                }
+                9 => {
+                    self.idx += 1;
+                    if self.args.skip != SearchArgs::DEFAULT_SKIP {
+                        return Some(("skip", self.args.skip.to_string()));
+                    }
+                }
+                10 => {
+                    self.idx += 1;
+                    if self.args.limit != SearchArgs::DEFAULT_LIMIT {
+                        return Some(("limit", self.args.limit.to_string()));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for SearchArgs {
+    type KeyValuePairs = SearchArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        SearchArgsIter { args: self, idx: 0 }
+    }
+}
@@ -0,0 +1,78 @@
+use super::{format_enum, Args, SortOrder, WordListSortType};
+
+#[derive(Clone, Debug)]
+pub struct WordListWordsArgs {
+    pub sort_by: Option<WordListSortType>,
+    pub sort_order: Option<SortOrder>,
+    pub skip: u32,
+    pub limit: u32,
+}
+
+impl WordListWordsArgs {
+    const DEFAULT_SKIP: u32 = 0;
+    const DEFAULT_LIMIT: u32 = 100;
+
+    pub fn new() -> Self {
+        Self {
+            sort_by: None,
+            sort_order: None,
+            skip: Self::DEFAULT_SKIP,
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+}
+
+impl Default for WordListWordsArgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct WordListWordsArgsIter<'a> {
+    args: &'a WordListWordsArgs,
+    idx: usize,
+}
+
+impl<'a> Iterator for WordListWordsArgsIter<'a> {
+    type Item = (&'static str, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.idx {
+                0 => {
+                    self.idx += 1;
+                    if let Some(sort_by) = self.args.sort_by {
+                        return Some(("sortBy", format_enum(&sort_by)));
+                    }
+                }
+                1 => {
+                    self.idx += 1;
+                    if let Some(sort_order) = self.args.sort_order {
+                        return Some(("sortOrder", format_enum(&sort_order)));
+                    }
+                }
+                2 => {
+                    self.idx += 1;
+                    if self.args.skip != WordListWordsArgs::DEFAULT_SKIP {
+                        return Some(("skip", self.args.skip.to_string()));
+                    }
+                }
+                3 => {
+                    self.idx += 1;
+                    if self.args.limit != WordListWordsArgs::DEFAULT_LIMIT {
+                        return Some(("limit", self.args.limit.to_string()));
+                    }
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Args<'a> for WordListWordsArgs {
+    type KeyValuePairs = WordListWordsArgsIter<'a>;
+
+    fn args(&'a self) -> Self::KeyValuePairs {
+        WordListWordsArgsIter { args: self, idx: 0 }
+    }
+}
@@ -0,0 +1,494 @@
+use std::{fmt, sync::Arc, time::SystemTime};
+
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "deprecated-endpoints")]
+use crate::{args::SearchArgs, model::WordSearchResults};
+use crate::{
+    args::{
+        AudioArgs, DefinitionsArgs, ExamplesArgs, FrequencyArgs, HyphenationArgs, PhrasesArgs,
+        PronunciationsArgs, RandomWordArgs, RandomWordsArgs, RelatedWordsArgs,
+        ReverseDictionaryArgs, WordListWordsArgs,
+    },
+    cache::{Cache, CacheStats},
+    date::Date,
+    endpoint::{self, Prepared, Request},
+    error::Error,
+    model::{
+        ApiTokenStatus, AudioFile, AuthenticationToken, Bigram, Definition,
+        DefinitionSearchResults, Etymology, Example, ExampleSearchResults, FrequencySummary,
+        Hyphenation, RandomWord, RelatedWords, ScrabbleScore, TextPron, WordList, WordListMetadata,
+        WordListWord, WordOfTheDay,
+    },
+    rate_limit::{QuotaStatus, RateLimiter},
+    transport::{HttpRequest, HttpResponse},
+    ApiKey, ClientBuilder, Result,
+};
+
+/// The async counterpart to `Client`, for use with tokio.
+///
+/// Every endpoint is available with the same name and arguments as on `Client`. The paging
+/// iterators (`examples_iter` and friends) are the exception; page through these by hand with
+/// `skip` and `limit`. Neither are there batch lookups like `definitions_many`: to look up many
+/// words at once, join the futures yourself and bound how many run at a time, for instance with
+/// `buffer_unordered` from the `futures` crate. Nothing here limits concurrency for you.
+///
+/// Failed requests aren't retried, and rate limits can't be waited for: both need a clock that
+/// can sleep, and this crate doesn't pick an async runtime for you. `build_async` refuses a
+/// retry policy, and rate limits other than those set to fail fast, which work as they do on
+/// `Client`. The quotas reported by the api are tracked either way; see `quota_status`.
+#[derive(Clone)]
+pub struct AsyncClient {
+    inner: reqwest::Client,
+    base_url: String,
+    pub(crate) api_key: ApiKey,
+
+    // Identifies a wordnik user, as opposed to the api key, which identifies the application.
+    // Only needed for word list management and private word lists.
+    auth_token: Option<String>,
+
+    // Sent with every request; see `ClientBuilder::default_header`.
+    headers: HeaderMap,
+
+    // Shared by a client's clones, so that they count against the same rate limits.
+    rate_limiter: Arc<RateLimiter>,
+    cache: Option<Arc<Cache>>,
+}
+
+impl fmt::Debug for AsyncClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncClient")
+            .field("inner", &self.inner)
+            .field("base_url", &self.base_url)
+            .field("api_key", &self.api_key)
+            .field(
+                "auth_token",
+                &self.auth_token.as_ref().map(|_| "<auth_token>"),
+            )
+            .field("rate_limiter", &self.rate_limiter)
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+impl AsyncClient {
+    pub(crate) fn stub_client(base_url: &str) -> Self {
+        Self::builder("stub_api_key")
+            .base_url(base_url)
+            .build_async()
+            .unwrap()
+    }
+}
+
+impl AsyncClient {
+    /// Creates a client with the default configuration.
+    ///
+    /// Panics if the underlying http client can't be created (e.g. because the TLS backend
+    /// fails to initialize); use `builder` to handle that case, or to change the configuration.
+    pub fn new(api_key: impl Into<ApiKey>) -> Self {
+        Self::builder(api_key).build_async().unwrap()
+    }
+
+    pub fn builder(api_key: impl Into<ApiKey>) -> ClientBuilder {
+        ClientBuilder::new(api_key)
+    }
+
+    pub(crate) fn from_parts(
+        inner: reqwest::Client,
+        base_url: String,
+        api_key: ApiKey,
+        auth_token: Option<String>,
+        headers: HeaderMap,
+        rate_limiter: Arc<RateLimiter>,
+        cache: Option<Arc<Cache>>,
+    ) -> Self {
+        Self {
+            inner,
+            base_url,
+            api_key,
+            auth_token,
+            headers,
+            rate_limiter,
+            cache,
+        }
+    }
+
+    /// Acts on behalf of the user the token belongs to. See `authenticate`.
+    pub fn with_auth_token(self, auth_token: impl Into<String>) -> Self {
+        Self {
+            auth_token: Some(auth_token.into()),
+            ..self
+        }
+    }
+
+    async fn execute(&self, request: Request) -> Result<HttpResponse> {
+        let prepared = endpoint::prepare(
+            request,
+            self.cache.as_deref(),
+            &self.base_url,
+            &self.api_key,
+            self.auth_token.as_deref(),
+            &self.headers,
+            SystemTime::now(),
+        )?;
+        match prepared {
+            Prepared::Cached(response) => Ok(response),
+            Prepared::Send(request, slot) => {
+                let response = self.send_http(request, true).await?;
+                endpoint::keep(slot, &response);
+                Ok(response)
+            }
+        }
+    }
+
+    /// How often the cache has answered in place of the api, if the client has one. Shared with
+    /// the client's clones.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// The call quotas reported by the most recent api response, if any has reported them yet.
+    /// Shared with the client's clones.
+    pub fn quota_status(&self) -> Option<QuotaStatus> {
+        self.rate_limiter.quota()
+    }
+
+    // Requests for anything but the api don't count against the rate limits.
+    async fn send_http(&self, request: HttpRequest, rate_limited: bool) -> Result<HttpResponse> {
+        if rate_limited {
+            self.rate_limiter.try_acquire(SystemTime::now())?;
+        }
+
+        let mut builder = self
+            .inner
+            .request(request.method, &request.url)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().await?;
+        if rate_limited {
+            self.rate_limiter
+                .observe(response.headers(), SystemTime::now());
+        }
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            headers: response.headers().clone(),
+            body: response.bytes().await?.to_vec(),
+        })
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: Request) -> Result<T> {
+        endpoint::decode(self.execute(request).await?)
+    }
+
+    async fn send_empty(&self, request: Request) -> Result<()> {
+        endpoint::check(self.execute(request).await?)?;
+        Ok(())
+    }
+
+    // Account API endpoint //
+
+    // post /account.json/authenticate/{username}
+    /// Exchanges a user's credentials for an auth token, for use with `with_auth_token`.
+    pub async fn authenticate(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<AuthenticationToken> {
+        self.send(endpoint::authenticate(username, password)).await
+    }
+
+    // get /account.json/apiTokenStatus
+    pub async fn api_token_status(&self) -> Result<ApiTokenStatus> {
+        self.send(endpoint::api_token_status()).await
+    }
+
+    // Word API endpoint //
+
+    // get /word.json/{word}/audio
+    pub async fn audio(&self, word: &str, args: &AudioArgs) -> Result<Vec<AudioFile>> {
+        self.send(endpoint::audio(word, args)).await
+    }
+
+    /// Downloads the audio file itself, using the same underlying client as everything else.
+    /// This doesn't count against the client's rate limits, since it isn't an api call.
+    ///
+    /// Wordnik only hands out file urls for a limited time, so this should be called shortly
+    /// after the call to `audio`.
+    pub async fn download_audio(&self, file: &AudioFile) -> Result<Vec<u8>> {
+        let url = file
+            .file_url
+            .as_deref()
+            .ok_or(Error::Api("audio file has no file url"))?;
+        let response = self
+            .send_http(endpoint::download(url, &self.headers), false)
+            .await?;
+        Ok(endpoint::check(response)?.body)
+    }
+
+    // get /word.json/{word}/definitions
+    pub async fn definitions(&self, word: &str) -> Result<Vec<Definition>> {
+        self.definitions_args(word, &DefinitionsArgs::new()).await
+    }
+
+    pub async fn definitions_args(
+        &self,
+        word: &str,
+        args: &DefinitionsArgs,
+    ) -> Result<Vec<Definition>> {
+        self.send(endpoint::definitions(word, args)).await
+    }
+
+    // get /word.json/{word}/etymologies
+    pub async fn etymologies(&self, word: &str) -> Result<Vec<Etymology>> {
+        self.send(endpoint::etymologies(word)).await
+    }
+
+    // get /word.json/{word}/examples
+    pub async fn examples(&self, word: &str, args: &ExamplesArgs) -> Result<ExampleSearchResults> {
+        self.send(endpoint::examples(word, args)).await
+    }
+
+    // get /word.json/{word}/frequency
+    pub async fn frequency(&self, word: &str, args: &FrequencyArgs) -> Result<FrequencySummary> {
+        self.send(endpoint::frequency(word, args)).await
+    }
+
+    // get /word.json/{word}/hyphenation
+    pub async fn hyphenation(&self, word: &str, args: &HyphenationArgs) -> Result<Hyphenation> {
+        self.send(endpoint::hyphenation(word, args)).await
+    }
+
+    // get /word.json/{word}/phrases
+    pub async fn phrases(&self, word: &str, args: &PhrasesArgs) -> Result<Vec<Bigram>> {
+        self.send(endpoint::phrases(word, args)).await
+    }
+
+    // get /word.json/{word}/pronunciations
+    pub async fn pronunciations(
+        &self,
+        word: &str,
+        args: &PronunciationsArgs,
+    ) -> Result<Vec<TextPron>> {
+        self.send(endpoint::pronunciations(word, args)).await
+    }
+
+    // get /word.json/{word}/relatedWords
+    pub async fn related_words(&self, word: &str, args: &RelatedWordsArgs) -> Result<RelatedWords> {
+        self.send(endpoint::related_words(word, args)).await
+    }
+
+    // get /word.json/{word}/scrabbleScore
+    pub async fn scrabble_score(&self, word: &str) -> Result<i32> {
+        let score: ScrabbleScore = self.send(endpoint::scrabble_score(word)).await?;
+        Ok(score.value)
+    }
+
+    // get /word.json/{word}/topExample
+    pub async fn top_example(&self, word: &str, use_canonical: bool) -> Result<Example> {
+        self.send(endpoint::top_example(word, use_canonical)).await
+    }
+
+    // Words API endpoint //
+
+    // get /words.json/randomWord
+    pub async fn random_word(&self) -> Result<RandomWord> {
+        self.random_word_args(&RandomWordArgs::new()).await
+    }
+
+    pub async fn random_word_args(&self, args: &RandomWordArgs) -> Result<RandomWord> {
+        self.send(endpoint::random_word(args)).await
+    }
+
+    // get /words.json/randomWords
+    pub async fn random_words(&self) -> Result<Vec<RandomWord>> {
+        self.random_words_args(&RandomWordsArgs::new()).await
+    }
+
+    pub async fn random_words_args(&self, args: &RandomWordsArgs) -> Result<Vec<RandomWord>> {
+        // I was wrong. I thought this came down as an xml blob, but it doesn't. No, sir: this
+        // gets sent over the wire as a JSON array of escaped XML strings, for all have sinned
+        // and fall short of the glory of God. I can't imagine what anyone would want this for,
+        // but here it is.
+
+        // Dw, it's all good, not like we know when the strings are actually XML blobs in the API
+        // documentation anyway.
+
+        self.send(endpoint::random_words(args)).await
+    }
+
+    // get /words.json/reverseDictionary
+    pub async fn reverse_dictionary(
+        &self,
+        args: &ReverseDictionaryArgs,
+    ) -> Result<DefinitionSearchResults> {
+        self.send(endpoint::reverse_dictionary(args)).await
+    }
+
+    // get /words.json/search/{query} (!! Deprecated for wordnik api v4 !!)
+    /// Searches for words matching a pattern, e.g. `"b*t"`.
+    ///
+    /// Wordnik has deprecated this endpoint, so it may stop working without notice; it's only
+    /// available with the `deprecated-endpoints` feature.
+    #[cfg(feature = "deprecated-endpoints")]
+    pub async fn search_words(&self, query: &str, args: &SearchArgs) -> Result<WordSearchResults> {
+        self.send(endpoint::search_words(query, args)).await
+    }
+
+    // get /words.json/wordOfTheDay
+    /// Fetches the word of the day for the given date, or for today if there isn't one.
+    pub async fn word_of_the_day(&self, date: Option<Date>) -> Result<WordOfTheDay> {
+        self.send(endpoint::word_of_the_day(date)).await
+    }
+
+    // Word List API endpoint //
+
+    // get /wordList.json/{permalink}
+    pub async fn word_list(&self, permalink: &str) -> Result<WordList> {
+        self.send(endpoint::word_list(permalink)).await
+    }
+
+    // get /wordList.json/{permalink}/words
+    pub async fn word_list_words(
+        &self,
+        permalink: &str,
+        args: &WordListWordsArgs,
+    ) -> Result<Vec<WordListWord>> {
+        self.send(endpoint::word_list_words(permalink, args)).await
+    }
+
+    // The remaining word list endpoints act on behalf of a user, and need an auth token.
+
+    // post /wordLists.json
+    pub async fn create_word_list(&self, list: &WordListMetadata) -> Result<WordList> {
+        self.send(endpoint::create_word_list(list)).await
+    }
+
+    // put /wordList.json/{permalink}
+    pub async fn update_word_list(&self, permalink: &str, list: &WordListMetadata) -> Result<()> {
+        self.send_empty(endpoint::update_word_list(permalink, list))
+            .await
+    }
+
+    // delete /wordList.json/{permalink}
+    pub async fn delete_word_list(&self, permalink: &str) -> Result<()> {
+        self.send_empty(endpoint::delete_word_list(permalink)).await
+    }
+
+    // post /wordList.json/{permalink}/words
+    pub async fn add_word_list_words(&self, permalink: &str, words: &[&str]) -> Result<()> {
+        self.send_empty(endpoint::add_word_list_words(permalink, words))
+            .await
+    }
+
+    // post /wordList.json/{permalink}/deleteWords
+    pub async fn delete_word_list_words(&self, permalink: &str, words: &[&str]) -> Result<()> {
+        self.send_empty(endpoint::delete_word_list_words(permalink, words))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::args::DefinitionsArgs;
+    use crate::error::Error;
+    use crate::model::AudioFile;
+    use crate::rate_limit::RateLimit;
+    use crate::test_server::{Reply, TestServer};
+
+    #[tokio::test]
+    async fn builds_the_same_requests_as_the_blocking_client() {
+        let server = TestServer::start(vec![Reply::json(
+            r#"[{ "word": "fireplace", "labels": [], "citations": [] }]"#,
+        )]);
+        let client = super::AsyncClient::stub_client(&server.base_url);
+
+        let mut args = DefinitionsArgs::new();
+        args.limit = 1;
+        let definitions = client.definitions_args("fireplace", &args).await.unwrap();
+        assert_eq!(definitions[0].word, "fireplace");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/word.json/fireplace/definitions?limit=1");
+    }
+
+    #[tokio::test]
+    async fn rate_limits_and_tracks_quota_against_stub() {
+        let server = TestServer::start(vec![Reply::json("[]")
+            .header("x-ratelimit-limit-minute", "100")
+            .header("x-ratelimit-remaining-minute", "42")]);
+        let client = super::AsyncClient::builder("stub_api_key")
+            .base_url(&server.base_url)
+            .rate_limit(RateLimit::per_minute(1).fail_fast())
+            .build_async()
+            .unwrap();
+
+        client.etymologies("horse").await.unwrap();
+        assert_eq!(client.quota_status().unwrap().minute_remaining, Some(42));
+        assert!(matches!(
+            client.etymologies("horse").await,
+            Err(Error::RateLimited(_))
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn audio_downloads_dont_count_against_rate_limits() {
+        let server = TestServer::start(vec![Reply::json("[]"), Reply::json("mp3")]);
+        let client = super::AsyncClient::builder("stub_api_key")
+            .base_url(&server.base_url)
+            .rate_limit(RateLimit::per_minute(1).fail_fast())
+            .build_async()
+            .unwrap();
+        let file: AudioFile = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "fileUrl": format!("{}/horse.mp3", server.base_url),
+        }))
+        .unwrap();
+
+        client.etymologies("horse").await.unwrap();
+        assert_eq!(client.download_audio(&file).await.unwrap(), b"mp3");
+    }
+
+    #[test]
+    fn refuses_rate_limits_that_wait() {
+        let res = super::AsyncClient::builder("stub_api_key")
+            .rate_limit(RateLimit::per_minute(1))
+            .build_async();
+        assert!(matches!(res, Err(Error::Api(_))));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn refuses_retry_policies() {
+        let res = super::AsyncClient::builder("stub_api_key")
+            .retry_policy(crate::retry::RetryPolicy::new())
+            .build_async();
+        assert!(matches!(res, Err(Error::Api(_))));
+
+        let res = super::AsyncClient::builder("stub_api_key")
+            .retry_policy(crate::retry::RetryPolicy::none())
+            .build_async();
+        assert!(res.is_ok());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn refuses_transports() {
+        let res = super::AsyncClient::builder("stub_api_key")
+            .transport(crate::transport::FixtureTransport::new())
+            .build_async();
+        assert!(matches!(res, Err(Error::Api(_))));
+    }
+
+    #[tokio::test]
+    async fn word_list_management_requires_auth_token() {
+        let client = super::AsyncClient::stub_client("http://127.0.0.1:9");
+        assert!(client.delete_word_list("my-list").await.is_err());
+    }
+}
@@ -0,0 +1,348 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, USER_AGENT},
+    Proxy,
+};
+
+#[cfg(feature = "async")]
+use crate::AsyncClient;
+use crate::{
+    cache::Cache,
+    error::Error,
+    rate_limit::{RateLimit, RateLimiter},
+    ApiKey, Result, API_BASE, USER_AGENT as DEFAULT_USER_AGENT,
+};
+#[cfg(feature = "blocking")]
+use crate::{
+    client::Pacing,
+    retry::{Clock, RetryPolicy, SystemClock},
+    transport::{ReqwestTransport, Transport},
+    Client,
+};
+
+/// Configures a `Client` (or `AsyncClient`) before building it.
+///
+/// ```no_run
+/// # #[cfg(feature = "blocking")]
+/// # fn main() -> wordnik::Result<()> {
+/// use std::time::Duration;
+///
+/// let client = wordnik::Client::builder("my-api-key")
+///     .base_url("http://localhost:8080/v4")
+///     .timeout(Duration::from_secs(10))
+///     .user_agent_suffix("my-app/1.0")
+///     .build()?;
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "blocking"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone)]
+pub struct ClientBuilder {
+    api_key: ApiKey,
+    base_url: String,
+    auth_token: Option<String>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    headers: HeaderMap,
+    user_agent_suffix: Option<String>,
+    cache: Option<Arc<Cache>>,
+    rate_limits: Vec<RateLimit>,
+    #[cfg(feature = "blocking")]
+    reqwest_client: Option<reqwest::blocking::Client>,
+    #[cfg(feature = "blocking")]
+    transport: Option<Arc<dyn Transport>>,
+    #[cfg(feature = "blocking")]
+    retry_policy: RetryPolicy,
+    #[cfg(feature = "blocking")]
+    clock: Arc<dyn Clock>,
+    #[cfg(feature = "async")]
+    async_reqwest_client: Option<reqwest::Client>,
+}
+
+impl fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("api_key", &self.api_key)
+            .field("base_url", &self.base_url)
+            .field(
+                "auth_token",
+                &self.auth_token.as_ref().map(|_| "<auth_token>"),
+            )
+            .field("connect_timeout", &self.connect_timeout)
+            .field("timeout", &self.timeout)
+            .field("proxy", &self.proxy)
+            .field("headers", &self.headers)
+            .field("user_agent_suffix", &self.user_agent_suffix)
+            .field("cache", &self.cache)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ClientBuilder {
+    pub fn new(api_key: impl Into<ApiKey>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            base_url: API_BASE.into(),
+            auth_token: None,
+            connect_timeout: None,
+            timeout: None,
+            proxy: None,
+            headers: HeaderMap::new(),
+            user_agent_suffix: None,
+            cache: None,
+            rate_limits: Vec::new(),
+            #[cfg(feature = "blocking")]
+            reqwest_client: None,
+            #[cfg(feature = "blocking")]
+            transport: None,
+            #[cfg(feature = "blocking")]
+            retry_policy: RetryPolicy::none(),
+            #[cfg(feature = "blocking")]
+            clock: Arc::new(SystemClock),
+            #[cfg(feature = "async")]
+            async_reqwest_client: None,
+        }
+    }
+
+    /// Where to send requests, e.g. a staging mirror or a local stub. Defaults to
+    /// `https://api.wordnik.com/v4`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').into();
+        self
+    }
+
+    /// Acts on behalf of the user the token belongs to. See `Client::authenticate`.
+    pub fn auth_token(mut self, auth_token: impl Into<String>) -> Self {
+        self.auth_token = Some(auth_token.into());
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Limits the time taken by each request as a whole, from connecting through reading the
+    /// last of the response.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Adds a header to every request.
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Appended to the user agent, e.g. "wordnik rust client v0.1.2 my-app/1.0".
+    pub fn user_agent_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.user_agent_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Keeps responses that rarely change, rather than asking the api again. See `cache::Cache`.
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Uses an existing client rather than building one. The timeouts and proxy are ignored in
+    /// this case, since they belong to the reqwest client.
+    #[cfg(feature = "blocking")]
+    pub fn reqwest_client(mut self, client: reqwest::blocking::Client) -> Self {
+        self.reqwest_client = Some(client);
+        self
+    }
+
+    /// Sends requests through `transport` rather than over http, e.g. a
+    /// `transport::FixtureTransport` for tests. The timeouts, proxy and reqwest client are
+    /// ignored in this case; the default headers and user agent are still sent.
+    ///
+    /// Transports are blocking, so `build_async` refuses to build with one rather than quietly
+    /// going over http.
+    #[cfg(feature = "blocking")]
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Retries requests that fail with e.g. a 503 or a 429. See `retry::RetryPolicy`; an
+    /// `AsyncClient` can't wait between retries, so `build_async` refuses any policy but `none`.
+    #[cfg(feature = "blocking")]
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Adds a limit on how often the client calls the api. See `rate_limit::RateLimit`; an
+    /// `AsyncClient` only takes limits that fail fast.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limits.push(limit);
+        self
+    }
+
+    /// Tells the time and waits between retries and for rate limits; only worth changing in
+    /// tests. Only `Client` uses it.
+    #[cfg(feature = "blocking")]
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Uses an existing client for `build_async`. See `reqwest_client`.
+    #[cfg(feature = "async")]
+    pub fn async_reqwest_client(mut self, client: reqwest::Client) -> Self {
+        self.async_reqwest_client = Some(client);
+        self
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn build(self) -> Result<Client> {
+        let headers = self.headers()?;
+        let transport = match self.transport.clone() {
+            Some(transport) => transport,
+            None => Arc::new(ReqwestTransport::new(self.blocking_client()?)),
+        };
+
+        Ok(Client::from_parts(
+            transport,
+            self.base_url,
+            self.api_key,
+            self.auth_token,
+            headers,
+            Pacing {
+                retry_policy: self.retry_policy,
+                rate_limiter: Arc::new(RateLimiter::new(self.rate_limits)),
+                clock: self.clock,
+            },
+            self.cache,
+        ))
+    }
+
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> Result<AsyncClient> {
+        #[cfg(feature = "blocking")]
+        if self.transport.is_some() {
+            return Err(Error::Api("async clients can't send through a transport"));
+        }
+        #[cfg(feature = "blocking")]
+        if self.retry_policy.retries() {
+            return Err(Error::Api("async clients can't retry requests"));
+        }
+        if !self.rate_limits.iter().all(RateLimit::is_fail_fast) {
+            return Err(Error::Api(
+                "async clients can't wait for rate limits; use RateLimit::fail_fast",
+            ));
+        }
+
+        let headers = self.headers()?;
+        let inner = match self.async_reqwest_client.clone() {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::Client::builder();
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(proxy) = self.proxy.clone() {
+                    builder = builder.proxy(proxy);
+                }
+                builder.build()?
+            }
+        };
+
+        Ok(AsyncClient::from_parts(
+            inner,
+            self.base_url,
+            self.api_key,
+            self.auth_token,
+            headers,
+            Arc::new(RateLimiter::new(self.rate_limits)),
+            self.cache,
+        ))
+    }
+
+    #[cfg(feature = "blocking")]
+    fn blocking_client(&self) -> Result<reqwest::blocking::Client> {
+        if let Some(client) = &self.reqwest_client {
+            return Ok(client.clone());
+        }
+
+        let mut builder = reqwest::blocking::Client::builder();
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = self.proxy.clone() {
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder.build()?)
+    }
+
+    fn headers(&self) -> Result<HeaderMap> {
+        let mut headers = self.headers.clone();
+        headers
+            .entry(ACCEPT)
+            .or_insert_with(|| HeaderValue::from_static("application/json"));
+
+        let user_agent = match &self.user_agent_suffix {
+            Some(suffix) => HeaderValue::from_str(&format!("{} {}", DEFAULT_USER_AGENT, suffix))
+                .map_err(|_| Error::Api("invalid user agent suffix"))?,
+            None => HeaderValue::from_static(DEFAULT_USER_AGENT),
+        };
+        headers.insert(USER_AGENT, user_agent);
+
+        Ok(headers)
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use reqwest::header::{HeaderName, HeaderValue};
+
+    use crate::test_server::{Reply, TestServer};
+
+    #[test]
+    fn builder_configures_client() {
+        let server = TestServer::start(vec![Reply::json("[]")]);
+        let client = super::ClientBuilder::new("builder_api_key")
+            .base_url(format!("{}/", server.base_url))
+            .user_agent_suffix("tests/1.0")
+            .default_header(
+                HeaderName::from_static("x-extra"),
+                HeaderValue::from_static("extra"),
+            )
+            .build()
+            .unwrap();
+
+        client.etymologies("horse").unwrap();
+
+        let request = &server.requests()[0];
+        assert_eq!(request.path, "/word.json/horse/etymologies");
+        assert_eq!(request.headers["api_key"], "builder_api_key");
+        assert!(request.headers["user-agent"].ends_with(" tests/1.0"));
+        assert_eq!(request.headers["x-extra"], "extra");
+        assert_eq!(request.headers["accept"], "application/json");
+    }
+
+    #[test]
+    fn builder_rejects_invalid_user_agent() {
+        let res = super::ClientBuilder::new("builder_api_key")
+            .user_agent_suffix("bad\nagent")
+            .build();
+        assert!(res.is_err());
+    }
+}
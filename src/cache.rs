@@ -0,0 +1,576 @@
+//! Caching responses for data that rarely changes, like a word's definitions or etymologies.
+//!
+//! Clients don't cache unless they're given a `Cache`:
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")]
+//! # fn main() -> wordnik::Result<()> {
+//! use std::time::Duration;
+//! use wordnik::cache::{Cache, Endpoint, MemoryCache};
+//!
+//! let cache = Cache::new(MemoryCache::new(1_000))
+//!     .ttl(Endpoint::Examples, Duration::from_secs(3_600))
+//!     .ttl(Endpoint::WordList, Duration::ZERO);
+//! let client = wordnik::Client::builder("my-api-key").cache(cache).build()?;
+//!
+//! client.definitions("fireplace")?;
+//! client.definitions("fireplace")?;
+//! assert_eq!(client.cache_stats().unwrap().hits, 1);
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "blocking"))]
+//! # fn main() {}
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "disk-cache")]
+use std::{
+    fs,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    process,
+};
+
+use reqwest::Method;
+
+use crate::{
+    endpoint::{Auth, Caching, Request},
+    transport::HttpResponse,
+};
+
+const DAY: Duration = Duration::from_secs(86_400);
+
+/// The endpoints whose responses can be cached.
+///
+/// Anything not listed here is never cached: the random word endpoints would be pointless to
+/// cache, audio file urls expire, and the account and word list management endpoints either
+/// change things or belong to a particular user.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Definitions,
+    Etymologies,
+    Examples,
+    Frequency,
+    Hyphenation,
+    Phrases,
+    Pronunciations,
+    RelatedWords,
+    ScrabbleScore,
+    TopExample,
+    ReverseDictionary,
+    #[cfg(feature = "deprecated-endpoints")]
+    SearchWords,
+    /// Today's word of the day is only ever cached until midnight UTC, when it changes.
+    WordOfTheDay,
+    /// Word lists are only cached for requests without an auth token.
+    WordList,
+    WordListWords,
+}
+
+impl Endpoint {
+    /// How long responses are kept unless the cache says otherwise.
+    pub fn default_ttl(self) -> Duration {
+        match self {
+            Endpoint::Examples | Endpoint::TopExample | Endpoint::ReverseDictionary => DAY,
+            #[cfg(feature = "deprecated-endpoints")]
+            Endpoint::SearchWords => DAY,
+            Endpoint::WordList | Endpoint::WordListWords => Duration::from_secs(300),
+            _ => DAY * 7,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub body: Vec<u8>,
+    pub expires: SystemTime,
+}
+
+/// Where cached responses are kept. Stores don't need to check expiry; the cache does that.
+pub trait CacheStore: fmt::Debug + Send + Sync {
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+    fn insert(&self, key: String, entry: CacheEntry);
+    fn remove(&self, key: &str);
+}
+
+/// Keeps up to `capacity` responses in memory, dropping the least recently used first.
+#[derive(Debug)]
+pub struct MemoryCache {
+    capacity: usize,
+    state: Mutex<Lru>,
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+    entries: HashMap<String, (CacheEntry, u64)>,
+    // Keys by when they were last used.
+    order: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl Lru {
+    fn touch(&mut self, key: &str) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some((_, used)) = self.entries.get_mut(key) {
+            self.order.remove(used);
+            self.order.insert(tick, key.into());
+            *used = tick;
+        }
+    }
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Default::default(),
+        }
+    }
+
+    fn remove_locked(&self, state: &mut Lru, key: &str) {
+        if let Some((_, used)) = state.entries.remove(key) {
+            state.order.remove(&used);
+        }
+    }
+}
+
+impl CacheStore for MemoryCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let mut state = self.state.lock().unwrap();
+        state.touch(key);
+        state.entries.get(key).map(|(entry, _)| entry.clone())
+    }
+
+    fn insert(&self, key: String, entry: CacheEntry) {
+        let mut state = self.state.lock().unwrap();
+        self.remove_locked(&mut state, &key);
+        state.entries.insert(key.clone(), (entry, 0));
+        state.touch(&key);
+
+        while state.entries.len() > self.capacity {
+            let oldest = match state.order.keys().next() {
+                Some(&oldest) => oldest,
+                None => break,
+            };
+            let key = state.order.remove(&oldest).unwrap();
+            state.entries.remove(&key);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let mut state = self.state.lock().unwrap();
+        self.remove_locked(&mut state, key);
+    }
+}
+
+/// Keeps responses as files in a directory, so that they survive the process.
+///
+/// Files are named for a hash of their key, and hold the key itself, so that a collision is only
+/// ever a miss. Errors reading or writing files are treated as misses too.
+///
+/// Expired files are swept away when the cache is opened and every so often as it's written
+/// to, and so are the files closest to expiring once there are more than `capacity`. Sweeps
+/// only touch files with the cache's own names, but the directory is best kept to itself.
+#[cfg(feature = "disk-cache")]
+#[derive(Debug)]
+pub struct DiskCache {
+    dir: PathBuf,
+    capacity: usize,
+    writes: AtomicU64,
+}
+
+#[cfg(feature = "disk-cache")]
+impl DiskCache {
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    /// Creates the directory if it doesn't exist yet, and sweeps it.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let cache = Self {
+            dir,
+            capacity: Self::DEFAULT_CAPACITY,
+            writes: AtomicU64::new(0),
+        };
+        cache.sweep()?;
+        Ok(cache)
+    }
+
+    /// Keeps up to about `capacity` responses. The limit is enforced by sweeps, so the
+    /// directory can hold a few more in between.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Removes expired and unreadable entries, temp files left behind by writers that died, and
+    /// then the entries closest to expiring until there are no more than `capacity`.
+    pub fn sweep(&self) -> io::Result<()> {
+        let now = SystemTime::now();
+        let mut entries = Vec::new();
+        for file in fs::read_dir(&self.dir)? {
+            let path = file?.path();
+            let name = match path.file_name().and_then(|name| name.to_str()) {
+                Some(name) if is_cache_file(name) => name,
+                _ => continue,
+            };
+
+            if name.ends_with(".tmp") {
+                let age = fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .map(|modified| now.duration_since(modified).unwrap_or_default());
+                if age.map_or(true, |age| age > ABANDONED) {
+                    let _ = fs::remove_file(&path);
+                }
+                continue;
+            }
+
+            match read_expiry(&path) {
+                Some(expires) if expires > now => entries.push((expires, path)),
+                _ => {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+
+        if entries.len() > self.capacity {
+            entries.sort();
+            let excess = entries.len() - self.capacity;
+            for (_, path) in &entries[..excess] {
+                let _ = fs::remove_file(path);
+            }
+        }
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(file_name(key))
+    }
+}
+
+// A temp file that hasn't been moved into place after this long belongs to a writer that died.
+#[cfg(feature = "disk-cache")]
+const ABANDONED: Duration = Duration::from_secs(60);
+
+// The 64-bit FNV-1a hash of the key. Unlike std's hashers, this is guaranteed not to change from
+// one release to the next, which would orphan every file in the cache.
+#[cfg(feature = "disk-cache")]
+fn file_name(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+// Entries are named by `file_name`, and temp files start out with the name of their entry.
+#[cfg(feature = "disk-cache")]
+fn is_cache_file(name: &str) -> bool {
+    let hash = name.get(..16).unwrap_or_default();
+    hash.len() == 16
+        && hash.bytes().all(|b| b.is_ascii_hexdigit())
+        && (name.len() == 16 || name.ends_with(".tmp"))
+}
+
+#[cfg(feature = "disk-cache")]
+fn read_expiry(path: &Path) -> Option<SystemTime> {
+    let mut line = String::new();
+    BufReader::new(fs::File::open(path).ok()?)
+        .read_line(&mut line)
+        .ok()?;
+    let expires = line.trim_end().parse().ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(expires))
+}
+
+// Files are the expiry in seconds since the epoch and the key, a line each, then the body.
+#[cfg(feature = "disk-cache")]
+impl CacheStore for DiskCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let contents = fs::read(self.path(key)).ok()?;
+        let mut parts = contents.splitn(3, |&b| b == b'\n');
+        let expires = std::str::from_utf8(parts.next()?).ok()?.parse().ok()?;
+        if parts.next()? != key.as_bytes() {
+            return None;
+        }
+
+        Some(CacheEntry {
+            body: parts.next()?.to_vec(),
+            expires: UNIX_EPOCH + Duration::from_secs(expires),
+        })
+    }
+
+    fn insert(&self, key: String, entry: CacheEntry) {
+        let expires = entry
+            .expires
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut contents = format!("{}\n{}\n", expires, key).into_bytes();
+        contents.extend_from_slice(&entry.body);
+
+        // Written aside and moved into place, so that readers never see half a file. Every write
+        // gets its own temp file, since another thread or process may be writing the same key.
+        static WRITES: AtomicU64 = AtomicU64::new(0);
+        let path = self.path(&key);
+        let temp = path.with_extension(format!(
+            "{}-{}.tmp",
+            process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        if fs::write(&temp, contents).is_ok() && fs::rename(&temp, &path).is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+
+        let sweep_every = (self.capacity as u64 / 10).clamp(1, 100);
+        if self.writes.fetch_add(1, Ordering::Relaxed) % sweep_every == sweep_every - 1 {
+            let _ = self.sweep();
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// A cache store, plus how long to keep each endpoint's responses.
+pub struct Cache {
+    store: Box<dyn CacheStore>,
+    ttls: HashMap<Endpoint, Duration>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl fmt::Debug for Cache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cache")
+            .field("store", &self.store)
+            .field("ttls", &self.ttls)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+// Where a cacheable request's response goes, and until when.
+pub(crate) struct Slot {
+    key: String,
+    expires: SystemTime,
+}
+
+impl Cache {
+    pub fn new(store: impl CacheStore + 'static) -> Self {
+        Self {
+            store: Box::new(store),
+            ttls: HashMap::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Overrides `Endpoint::default_ttl`. A ttl of zero turns caching off for the endpoint.
+    pub fn ttl(mut self, endpoint: Endpoint, ttl: Duration) -> Self {
+        self.ttls.insert(endpoint, ttl);
+        self
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Where to find and keep the response to `request`; `None` if it isn't to be cached.
+    pub(crate) fn slot(
+        &self,
+        request: &Request,
+        base_url: &str,
+        has_auth_token: bool,
+        now: SystemTime,
+    ) -> Option<Slot> {
+        if request.method != Method::GET || (has_auth_token && request.auth != Auth::None) {
+            return None;
+        }
+
+        let (endpoint, until_midnight) = match request.caching {
+            Caching::Never => return None,
+            Caching::For(endpoint) => (endpoint, false),
+            Caching::UntilMidnight(endpoint) => (endpoint, true),
+        };
+        let ttl = self
+            .ttls
+            .get(&endpoint)
+            .copied()
+            .unwrap_or_else(|| endpoint.default_ttl());
+        if ttl == Duration::ZERO {
+            return None;
+        }
+
+        let mut expires = now + ttl;
+        if until_midnight {
+            expires = expires.min(next_midnight(now));
+        }
+        Some(Slot {
+            key: request.cache_key(base_url),
+            expires,
+        })
+    }
+
+    pub(crate) fn get(&self, slot: &Slot, now: SystemTime) -> Option<HttpResponse> {
+        match self.store.get(&slot.key) {
+            Some(entry) if entry.expires > now => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(HttpResponse::new(200, entry.body))
+            }
+            stale => {
+                if stale.is_some() {
+                    self.store.remove(&slot.key);
+                }
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Keeps `response` if it was a success.
+    pub(crate) fn insert(&self, slot: Slot, response: &HttpResponse) {
+        if response.is_success() {
+            let entry = CacheEntry {
+                body: response.body.clone(),
+                expires: slot.expires,
+            };
+            self.store.insert(slot.key, entry);
+        }
+    }
+}
+
+fn next_midnight(now: SystemTime) -> SystemTime {
+    let days = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / DAY.as_secs();
+    UNIX_EPOCH + DAY * (days as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{CacheEntry, CacheStore, MemoryCache};
+
+    fn entry(body: &str) -> CacheEntry {
+        CacheEntry {
+            body: body.into(),
+            expires: UNIX_EPOCH + Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn memory_cache_drops_least_recently_used() {
+        let cache = MemoryCache::new(2);
+        cache.insert("a".into(), entry("a"));
+        cache.insert("b".into(), entry("b"));
+        assert!(cache.get("a").is_some());
+
+        cache.insert("c".into(), entry("c"));
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.get("a"), Some(entry("a")));
+        assert_eq!(cache.get("c"), Some(entry("c")));
+
+        cache.remove("a");
+        assert!(cache.get("a").is_none());
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[test]
+    fn disk_cache_round_trips() {
+        use super::DiskCache;
+
+        let dir = std::env::temp_dir().join(format!("wordnik-cache-{}", std::process::id()));
+        let cache = DiskCache::new(&dir).unwrap();
+        let key = "http://localhost/word.json/horse/etymologies";
+        cache.insert(key.into(), entry("[1,\n2]"));
+        assert_eq!(cache.get(key), Some(entry("[1,\n2]")));
+        assert!(cache
+            .get("http://localhost/word.json/cart/etymologies")
+            .is_none());
+
+        cache.remove(key);
+        assert!(cache.get(key).is_none());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[test]
+    fn disk_cache_writers_of_one_key_dont_mix() {
+        use super::DiskCache;
+        use std::time::SystemTime;
+
+        let dir = std::env::temp_dir().join(format!("wordnik-cache-race-{}", std::process::id()));
+        let cache = DiskCache::new(&dir).unwrap();
+        let key = "http://localhost/word.json/fireplace/definitions";
+        let body = "x".repeat(1 << 16);
+        let entry = CacheEntry {
+            body: body.clone().into_bytes(),
+            expires: SystemTime::now() + Duration::from_secs(60),
+        };
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..20 {
+                        cache.insert(key.into(), entry.clone());
+                        if let Some(read) = cache.get(key) {
+                            assert_eq!(read.body.len(), body.len());
+                        }
+                    }
+                });
+            }
+        });
+
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[test]
+    fn disk_cache_sweeps_expired_and_excess_entries() {
+        use super::{file_name, DiskCache};
+        use std::time::SystemTime;
+
+        let dir = std::env::temp_dir().join(format!("wordnik-cache-sweep-{}", std::process::id()));
+        let cache = DiskCache::new(&dir).unwrap().capacity(2);
+        let expiring_in = |secs| CacheEntry {
+            body: b"[]".to_vec(),
+            expires: SystemTime::now() + Duration::from_secs(secs),
+        };
+        cache.insert("expired".into(), entry("[]"));
+        cache.insert("soon".into(), expiring_in(60));
+        cache.insert("later".into(), expiring_in(120));
+        cache.insert("latest".into(), expiring_in(180));
+        std::fs::write(dir.join("not-ours.txt"), "keep me").unwrap();
+
+        cache.sweep().unwrap();
+        assert!(cache.get("expired").is_none());
+        assert!(cache.get("soon").is_none());
+        assert!(cache.get("later").is_some());
+        assert!(cache.get("latest").is_some());
+        assert!(dir.join("not-ours.txt").exists());
+
+        // Names have to stay the same across releases, or caches would be orphaned.
+        assert_eq!(file_name(""), "cbf29ce484222325");
+        assert_eq!(file_name("a"), "af63dc4c8601ec8c");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
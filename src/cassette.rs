@@ -0,0 +1,412 @@
+//! Recording real responses and replaying them, so that tests can run without an api key or a
+//! network connection.
+//!
+//! A `Cassette` is a `Transport` with two modes. When recording, it passes requests on to another
+//! transport and writes each request and its response to a file as it goes. When replaying, it
+//! answers requests from that file. `Cassette::from_env` picks the mode from the
+//! `WORDNIK_CASSETTE` environment variable, so one set of tests can do either:
+//!
+//! ```no_run
+//! # #[cfg(feature = "blocking")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use wordnik::{cassette::Cassette, transport::ReqwestTransport, Client};
+//!
+//! // `WORDNIK_CASSETTE=record cargo test` calls the api and rewrites the cassette;
+//! // `cargo test` replays it.
+//! let cassette = Cassette::from_env(
+//!     "tests/cassettes/definitions.json",
+//!     ReqwestTransport::new(Default::default()),
+//! )?;
+//! let api_key = std::env::var("WORDNIK_API_KEY").unwrap_or_default();
+//! let client = Client::builder(api_key).transport(cassette).build()?;
+//!
+//! client.definitions("fireplace")?;
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "blocking"))]
+//! # fn main() {}
+//! ```
+//!
+//! Credentials stay out of the file. The values of the `api_key` and `auth_token` headers are
+//! replaced with `<scrubbed>` wherever they turn up, and so is any `token` field in a json
+//! response, like the one `Client::authenticate` gets back. Urls in json responses lose their
+//! query strings, which is where signed urls like audio files' keep their signatures, and so do
+//! requests for anything but the api, such as those audio files. Request headers and bodies aren't
+//! recorded at all, so requests are replayed by method and url alone; where the same request was
+//! recorded more than once, its responses come back in the order they were recorded.
+
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    endpoint::{API_KEY, AUTH_TOKEN},
+    error::Error,
+    transport::{HttpRequest, HttpResponse, Transport},
+    Result,
+};
+
+/// The environment variable `Cassette::from_env` reads.
+pub static MODE_VAR: &str = "WORDNIK_CASSETTE";
+
+static SCRUBBED: &str = "<scrubbed>";
+static NOT_RECORDED: &str = "the cassette has no recording of this request";
+static WRITE_FAILED: &str = "unable to write the cassette";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Record,
+    Replay,
+}
+
+impl Mode {
+    /// Reads `WORDNIK_CASSETTE`, which should be "record" or "replay". Unset means replay, so
+    /// that tests run offline unless asked not to.
+    pub fn from_env() -> io::Result<Self> {
+        match env::var(MODE_VAR).as_deref() {
+            Err(_) | Ok("") | Ok("replay") => Ok(Mode::Replay),
+            Ok("record") => Ok(Mode::Record),
+            Ok(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "WORDNIK_CASSETTE should be \"record\" or \"replay\"",
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Tape {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+    #[serde(skip)]
+    played: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    body: Body,
+}
+
+// Json bodies are kept as json, so that cassettes can be read and edited by hand.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Body {
+    Json(Value),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl RecordedResponse {
+    fn new(response: &HttpResponse, secrets: &[String]) -> Self {
+        let headers = response
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.to_string(), scrub(value.to_str().ok()?, secrets)))
+            })
+            .collect();
+
+        let body = match std::str::from_utf8(&response.body) {
+            Ok(text) => {
+                let text = scrub(text, secrets);
+                match serde_json::from_str(&text) {
+                    Ok(mut json) => {
+                        scrub_json(&mut json);
+                        Body::Json(json)
+                    }
+                    Err(_) => Body::Text(text),
+                }
+            }
+            Err(_) => Body::Bytes(response.body.clone()),
+        };
+
+        Self {
+            status: response.status,
+            headers,
+            body,
+        }
+    }
+
+    fn to_http(&self) -> HttpResponse {
+        let body = match &self.body {
+            Body::Json(json) => json.to_string().into_bytes(),
+            Body::Text(text) => text.clone().into_bytes(),
+            Body::Bytes(bytes) => bytes.clone(),
+        };
+
+        let mut response = HttpResponse::new(self.status, body);
+        response.headers = self
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect();
+        response
+    }
+}
+
+/// A transport that records requests and responses to a file, or replays them from one. See the
+/// module documentation.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    // What's recorded from; `None` when replaying.
+    inner: Option<Box<dyn Transport>>,
+    tape: Mutex<Tape>,
+}
+
+impl Cassette {
+    /// Sends requests through `inner`, writing what happens to `path` after every request.
+    /// Anything already at `path` is replaced.
+    pub fn record(path: impl Into<PathBuf>, inner: impl Transport + 'static) -> io::Result<Self> {
+        let cassette = Self {
+            path: path.into(),
+            inner: Some(Box::new(inner)),
+            tape: Mutex::new(Tape::default()),
+        };
+        cassette.save(&Tape::default())?;
+        Ok(cassette)
+    }
+
+    /// Answers requests from the cassette at `path`. Requests it has no recording of fail with
+    /// an `Error::Api`.
+    pub fn replay(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let tape = serde_json::from_slice(&fs::read(&path)?)?;
+        Ok(Self {
+            path,
+            inner: None,
+            tape: Mutex::new(tape),
+        })
+    }
+
+    /// Records through `inner` or replays, according to `Mode::from_env`.
+    pub fn from_env(path: impl Into<PathBuf>, inner: impl Transport + 'static) -> io::Result<Self> {
+        match Mode::from_env()? {
+            Mode::Record => Self::record(path, inner),
+            Mode::Replay => Self::replay(path),
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        match self.inner {
+            Some(_) => Mode::Record,
+            None => Mode::Replay,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn save(&self, tape: &Tape) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut contents = serde_json::to_vec_pretty(tape)?;
+        contents.push(b'\n');
+        fs::write(&self.path, contents)
+    }
+}
+
+impl Transport for Cassette {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let secrets = secrets(&request.headers);
+        let method = request.method.to_string();
+        let mut url = scrub(&request.url, &secrets);
+        // Api requests carry the api key; anything else is probably a url the api handed out.
+        if !request.headers.contains_key(API_KEY) {
+            url = without_query(&url).into();
+        }
+
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => {
+                let mut tape = self.tape.lock().unwrap();
+                let matches = |interaction: &Interaction| {
+                    interaction.request.method == method && interaction.request.url == url
+                };
+                let idx = tape
+                    .interactions
+                    .iter()
+                    .position(|interaction| !interaction.played && matches(interaction))
+                    .or_else(|| tape.interactions.iter().rposition(matches))
+                    .ok_or(Error::Api(NOT_RECORDED))?;
+
+                let interaction = &mut tape.interactions[idx];
+                interaction.played = true;
+                return Ok(interaction.response.to_http());
+            }
+        };
+
+        // Failures without a response aren't recorded; there's nothing to replay.
+        let response = inner.send(request)?;
+        let mut tape = self.tape.lock().unwrap();
+        tape.interactions.push(Interaction {
+            request: RecordedRequest { method, url },
+            response: RecordedResponse::new(&response, &secrets),
+            played: false,
+        });
+        self.save(&tape).map_err(|_| Error::Api(WRITE_FAILED))?;
+        Ok(response)
+    }
+}
+
+fn secrets(headers: &HeaderMap) -> Vec<String> {
+    [API_KEY, AUTH_TOKEN]
+        .iter()
+        .filter_map(|&name| headers.get(name)?.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn scrub(text: &str, secrets: &[String]) -> String {
+    secrets.iter().fold(text.to_string(), |text, secret| {
+        text.replace(secret.as_str(), SCRUBBED)
+    })
+}
+
+fn without_query(url: &str) -> &str {
+    url.split('?').next().unwrap_or_default()
+}
+
+// Scrubs `token` fields and the query strings of urls.
+fn scrub_json(json: &mut Value) {
+    match json {
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                if name == "token" && value.is_string() {
+                    *value = Value::String(SCRUBBED.into());
+                } else {
+                    scrub_json(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(scrub_json),
+        Value::String(text) if text.starts_with("http://") || text.starts_with("https://") => {
+            *text = without_query(text).into();
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::Cassette;
+    use crate::{args::AudioArgs, error::Error, transport::FixtureTransport, Client};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("wordnik-cassettes-{}", std::process::id()))
+            .join(name)
+    }
+
+    fn fixtures() -> FixtureTransport {
+        FixtureTransport::new()
+            .with_body("/word.json/horse/etymologies", r#"["<ety>horse</ety>"]"#)
+            .with_body(
+                "/account.json/apiTokenStatus",
+                r#"{"valid":true,"token":"cassette-secret","remainingCalls":10}"#,
+            )
+            .with_body(
+                "/account.json/authenticate/someone",
+                r#"{"token":"user-token","userId":1}"#,
+            )
+            .with_body(
+                "/word.json/horse/audio",
+                r#"[{"id":1,"fileUrl":"https://audio.example/horse.mp3?sig=signature"}]"#,
+            )
+            .with_body("/horse.mp3", "mp3")
+    }
+
+    #[test]
+    fn records_without_credentials_then_replays() {
+        let path = temp_path("round_trip.json");
+        let client = Client::builder("cassette-secret")
+            .transport(Cassette::record(&path, fixtures()).unwrap())
+            .build()
+            .unwrap();
+        client.etymologies("horse").unwrap();
+        client.api_token_status().unwrap();
+        client.authenticate("someone", "password").unwrap();
+        let audio = client.audio("horse", &AudioArgs::new()).unwrap();
+        client.download_audio(&audio[0]).unwrap();
+        assert!(client.etymologies("cart").is_err());
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("cassette-secret"));
+        assert!(!contents.contains("user-token"));
+        assert!(!contents.contains("password"));
+        assert!(!contents.contains("signature"));
+
+        let client = Client::builder("another-key")
+            .transport(Cassette::replay(&path).unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(client.etymologies("horse").unwrap().len(), 1);
+        assert!(client.api_token_status().unwrap().valid);
+        let audio = client.audio("horse", &AudioArgs::new()).unwrap();
+        assert_eq!(client.download_audio(&audio[0]).unwrap(), b"mp3");
+        assert!(matches!(
+            client.etymologies("cart"),
+            Err(Error::Status(404))
+        ));
+        assert!(matches!(client.definitions("horse"), Err(Error::Api(_))));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn replays_repeated_requests_in_order() {
+        let path = temp_path("in_order.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            r#"{"interactions": [
+                {"request": {"method": "GET", "url": "http://localhost/words.json/randomWord"},
+                 "response": {"status": 200, "body": {"json": {"id": 1, "word": "first"}}}},
+                {"request": {"method": "GET", "url": "http://localhost/words.json/randomWord"},
+                 "response": {"status": 200, "body": {"json": {"id": 2, "word": "second"}}}}
+            ]}"#,
+        )
+        .unwrap();
+
+        let client = Client::builder("any-key")
+            .base_url("http://localhost")
+            .transport(Cassette::replay(&path).unwrap())
+            .build()
+            .unwrap();
+        let words: Vec<_> = (0..3).map(|_| client.random_word().unwrap().word).collect();
+        assert_eq!(words, ["first", "second", "second"]);
+
+        fs::remove_file(path).unwrap();
+    }
+}
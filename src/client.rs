@@ -0,0 +1,519 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "deprecated-endpoints")]
+use crate::{args::SearchArgs, model::WordSearchResults};
+use crate::{
+    args::{
+        AudioArgs, DefinitionsArgs, ExamplesArgs, FrequencyArgs, HyphenationArgs, PhrasesArgs,
+        PronunciationsArgs, RandomWordArgs, RandomWordsArgs, RelatedWordsArgs,
+        ReverseDictionaryArgs, WordListWordsArgs,
+    },
+    cache::{Cache, CacheStats},
+    date::Date,
+    endpoint::{self, Prepared, Request},
+    error::Error,
+    iter::{Examples, WordListWords, WordsOfTheDay},
+    model::{
+        ApiTokenStatus, AudioFile, AuthenticationToken, Bigram, Definition,
+        DefinitionSearchResults, Etymology, Example, ExampleSearchResults, FrequencySummary,
+        Hyphenation, RandomWord, RelatedWords, ScrabbleScore, TextPron, WordList, WordListMetadata,
+        WordListWord, WordOfTheDay,
+    },
+    rate_limit::{QuotaStatus, RateLimiter},
+    retry::{Clock, RetryPolicy},
+    transport::{HttpRequest, HttpResponse, Transport},
+    ApiKey, ClientBuilder, Result,
+};
+
+#[cfg(test)]
+use crate::{
+    cassette::{Cassette, Mode},
+    transport::ReqwestTransport,
+};
+#[cfg(test)]
+use std::{path::Path, sync::OnceLock};
+
+#[cfg(test)]
+static WORDNIK_API_KEY: &str = "WORDNIK_API_KEY";
+#[cfg(test)]
+static LIVE_CASSETTE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/resource/cassettes/live.json");
+#[cfg(test)]
+static SYNTHETIC_CASSETTE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/resource/cassettes/synthetic.json"
+);
+
+#[derive(Clone)]
+pub struct Client {
+    transport: Arc<dyn Transport>,
+    base_url: String,
+    pub(crate) api_key: ApiKey,
+
+    // Identifies a wordnik user, as opposed to the api key, which identifies the application.
+    // Only needed for word list management and private word lists.
+    auth_token: Option<String>,
+
+    // Sent with every request; see `ClientBuilder::default_header`.
+    headers: HeaderMap,
+
+    pacing: Pacing,
+    cache: Option<Arc<Cache>>,
+}
+
+// When and how often a client sends its requests, as set up by `ClientBuilder`. Shared by a
+// client's clones, so that they count against the same rate limits.
+#[derive(Clone, Debug)]
+pub(crate) struct Pacing {
+    pub retry_policy: RetryPolicy,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub clock: Arc<dyn Clock>,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("transport", &self.transport)
+            .field("base_url", &self.base_url)
+            .field("api_key", &self.api_key)
+            .field(
+                "auth_token",
+                &self.auth_token.as_ref().map(|_| "<auth_token>"),
+            )
+            .field("pacing", &self.pacing)
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+impl Client {
+    // Replays resource/cassettes/live.json, a recording of the real api, if there is one.
+    // Otherwise replays resource/cassettes/synthetic.json, which is hand-written: it has the
+    // requests the tests make, but made-up responses, so it only shows that the client can read
+    // responses of the right shape. WORDNIK_CASSETTE=record and a real api key make (or remake)
+    // the recording. The clients share one cassette, so that it has every test's requests in it.
+    pub(crate) fn test_client() -> Self {
+        static CASSETTE: OnceLock<Arc<Cassette>> = OnceLock::new();
+
+        dotenv::dotenv().ok();
+        let cassette = CASSETTE.get_or_init(|| {
+            let live = Path::new(LIVE_CASSETTE);
+            let cassette = match Mode::from_env().unwrap() {
+                Mode::Record => Cassette::record(live, ReqwestTransport::new(Default::default())),
+                Mode::Replay if live.exists() => Cassette::replay(live),
+                Mode::Replay => Cassette::replay(SYNTHETIC_CASSETTE),
+            };
+            Arc::new(cassette.unwrap())
+        });
+        let api_key = match cassette.mode() {
+            Mode::Record => dotenv::var(WORDNIK_API_KEY).unwrap(),
+            Mode::Replay => "replay_api_key".into(),
+        };
+
+        Self::builder(api_key)
+            .transport(cassette.clone())
+            .build()
+            .unwrap()
+    }
+
+    pub(crate) fn stub_client(base_url: &str) -> Self {
+        Self::builder("stub_api_key")
+            .base_url(base_url)
+            .build()
+            .unwrap()
+    }
+}
+
+impl Client {
+    /// Creates a client with the default configuration.
+    ///
+    /// Panics if the underlying http client can't be created (e.g. because the TLS backend
+    /// fails to initialize); use `builder` to handle that case, or to change the configuration.
+    pub fn new(api_key: impl Into<ApiKey>) -> Self {
+        Self::builder(api_key).build().unwrap()
+    }
+
+    pub fn builder(api_key: impl Into<ApiKey>) -> ClientBuilder {
+        ClientBuilder::new(api_key)
+    }
+
+    pub(crate) fn from_parts(
+        transport: Arc<dyn Transport>,
+        base_url: String,
+        api_key: ApiKey,
+        auth_token: Option<String>,
+        headers: HeaderMap,
+        pacing: Pacing,
+        cache: Option<Arc<Cache>>,
+    ) -> Self {
+        Self {
+            transport,
+            base_url,
+            api_key,
+            auth_token,
+            headers,
+            pacing,
+            cache,
+        }
+    }
+
+    /// Acts on behalf of the user the token belongs to. See `authenticate`.
+    pub fn with_auth_token(self, auth_token: impl Into<String>) -> Self {
+        Self {
+            auth_token: Some(auth_token.into()),
+            ..self
+        }
+    }
+
+    fn execute(&self, request: Request) -> Result<HttpResponse> {
+        let prepared = endpoint::prepare(
+            request,
+            self.cache.as_deref(),
+            &self.base_url,
+            &self.api_key,
+            self.auth_token.as_deref(),
+            &self.headers,
+            self.pacing.clock.now(),
+        )?;
+        match prepared {
+            Prepared::Cached(response) => Ok(response),
+            Prepared::Send(request, slot) => {
+                let response = self.send_http(request, true)?;
+                endpoint::keep(slot, &response);
+                Ok(response)
+            }
+        }
+    }
+
+    // Sends a request as many times as the retry policy allows, each time within the rate
+    // limits if it's `rate_limited`; requests for anything but the api don't count against them.
+    fn send_http(&self, request: HttpRequest, rate_limited: bool) -> Result<HttpResponse> {
+        let Pacing {
+            retry_policy,
+            rate_limiter,
+            clock,
+        } = &self.pacing;
+
+        let mut attempt = 1;
+        loop {
+            if rate_limited {
+                rate_limiter.acquire(clock.as_ref())?;
+            }
+            let result = self.transport.send(request.clone());
+            if let (true, Ok(response)) = (rate_limited, &result) {
+                rate_limiter.observe(&response.headers, clock.now());
+            }
+
+            match retry_policy.delay(attempt, &request.method, &result, clock.now()) {
+                Some(delay) => clock.sleep(delay),
+                None => return result,
+            }
+            attempt += 1;
+        }
+    }
+
+    /// How often the cache has answered in place of the api, if the client has one. Shared with
+    /// the client's clones.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// The call quotas reported by the most recent api response, if any has reported them yet.
+    /// Shared with the client's clones.
+    pub fn quota_status(&self) -> Option<QuotaStatus> {
+        self.pacing.rate_limiter.quota()
+    }
+
+    fn send<T: DeserializeOwned>(&self, request: Request) -> Result<T> {
+        endpoint::decode(self.execute(request)?)
+    }
+
+    fn send_empty(&self, request: Request) -> Result<()> {
+        endpoint::check(self.execute(request)?)?;
+        Ok(())
+    }
+
+    // Account API endpoint //
+
+    // post /account.json/authenticate/{username}
+    /// Exchanges a user's credentials for an auth token, for use with `with_auth_token`.
+    pub fn authenticate(&self, username: &str, password: &str) -> Result<AuthenticationToken> {
+        self.send(endpoint::authenticate(username, password))
+    }
+
+    // get /account.json/apiTokenStatus
+    pub fn api_token_status(&self) -> Result<ApiTokenStatus> {
+        self.send(endpoint::api_token_status())
+    }
+
+    // Word API endpoint //
+
+    // get /word.json/{word}/audio
+    pub fn audio(&self, word: &str, args: &AudioArgs) -> Result<Vec<AudioFile>> {
+        self.send(endpoint::audio(word, args))
+    }
+
+    /// Downloads the audio file itself, using the same transport as everything else. This
+    /// doesn't count against the client's rate limits, since it isn't an api call.
+    ///
+    /// Wordnik only hands out file urls for a limited time, so this should be called shortly
+    /// after the call to `audio`.
+    pub fn download_audio(&self, file: &AudioFile) -> Result<Vec<u8>> {
+        let url = file
+            .file_url
+            .as_deref()
+            .ok_or(Error::Api("audio file has no file url"))?;
+        let response = self.send_http(endpoint::download(url, &self.headers), false)?;
+        Ok(endpoint::check(response)?.body)
+    }
+
+    // get /word.json/{word}/definitions
+    pub fn definitions(&self, word: &str) -> Result<Vec<Definition>> {
+        self.definitions_args(word, &DefinitionsArgs::new())
+    }
+
+    pub fn definitions_args(&self, word: &str, args: &DefinitionsArgs) -> Result<Vec<Definition>> {
+        self.send(endpoint::definitions(word, args))
+    }
+
+    /// Looks up the definitions of many words at once, on up to `concurrency` threads.
+    ///
+    /// The results are in the same order as `words`, and a failure for one word doesn't stop
+    /// the rest. Every request counts against the client's rate limits, so a limit that waits
+    /// slows the batch down, while one that fails fast fails the words that don't fit.
+    pub fn definitions_many<W: AsRef<str> + Sync>(
+        &self,
+        words: &[W],
+        args: &DefinitionsArgs,
+        concurrency: usize,
+    ) -> Vec<Result<Vec<Definition>>> {
+        fan_out(words, concurrency, |word| self.definitions_args(word, args))
+    }
+
+    // get /word.json/{word}/etymologies
+    pub fn etymologies(&self, word: &str) -> Result<Vec<Etymology>> {
+        self.send(endpoint::etymologies(word))
+    }
+
+    /// See `definitions_many`.
+    pub fn etymologies_many<W: AsRef<str> + Sync>(
+        &self,
+        words: &[W],
+        concurrency: usize,
+    ) -> Vec<Result<Vec<Etymology>>> {
+        fan_out(words, concurrency, |word| self.etymologies(word))
+    }
+
+    // get /word.json/{word}/examples
+    pub fn examples(&self, word: &str, args: &ExamplesArgs) -> Result<ExampleSearchResults> {
+        self.send(endpoint::examples(word, args))
+    }
+
+    /// Iterates over every example for a word, fetching pages of `args.limit` examples as
+    /// needed.
+    pub fn examples_iter(&self, word: &str, args: &ExamplesArgs) -> Examples<'_> {
+        Examples::new(self, word, args)
+    }
+
+    // get /word.json/{word}/frequency
+    pub fn frequency(&self, word: &str, args: &FrequencyArgs) -> Result<FrequencySummary> {
+        self.send(endpoint::frequency(word, args))
+    }
+
+    // get /word.json/{word}/hyphenation
+    pub fn hyphenation(&self, word: &str, args: &HyphenationArgs) -> Result<Hyphenation> {
+        self.send(endpoint::hyphenation(word, args))
+    }
+
+    // get /word.json/{word}/phrases
+    pub fn phrases(&self, word: &str, args: &PhrasesArgs) -> Result<Vec<Bigram>> {
+        self.send(endpoint::phrases(word, args))
+    }
+
+    // get /word.json/{word}/pronunciations
+    pub fn pronunciations(&self, word: &str, args: &PronunciationsArgs) -> Result<Vec<TextPron>> {
+        self.send(endpoint::pronunciations(word, args))
+    }
+
+    /// See `definitions_many`.
+    pub fn pronunciations_many<W: AsRef<str> + Sync>(
+        &self,
+        words: &[W],
+        args: &PronunciationsArgs,
+        concurrency: usize,
+    ) -> Vec<Result<Vec<TextPron>>> {
+        fan_out(words, concurrency, |word| self.pronunciations(word, args))
+    }
+
+    // get /word.json/{word}/relatedWords
+    pub fn related_words(&self, word: &str, args: &RelatedWordsArgs) -> Result<RelatedWords> {
+        self.send(endpoint::related_words(word, args))
+    }
+
+    // get /word.json/{word}/scrabbleScore
+    pub fn scrabble_score(&self, word: &str) -> Result<i32> {
+        let score: ScrabbleScore = self.send(endpoint::scrabble_score(word))?;
+        Ok(score.value)
+    }
+
+    // get /word.json/{word}/topExample
+    pub fn top_example(&self, word: &str, use_canonical: bool) -> Result<Example> {
+        self.send(endpoint::top_example(word, use_canonical))
+    }
+
+    // Words API endpoint //
+
+    // get /words.json/randomWord
+    pub fn random_word(&self) -> Result<RandomWord> {
+        self.random_word_args(&RandomWordArgs::new())
+    }
+
+    pub fn random_word_args(&self, args: &RandomWordArgs) -> Result<RandomWord> {
+        self.send(endpoint::random_word(args))
+    }
+
+    // get /words.json/randomWords
+    pub fn random_words(&self) -> Result<Vec<RandomWord>> {
+        self.random_words_args(&RandomWordsArgs::new())
+    }
+
+    pub fn random_words_args(&self, args: &RandomWordsArgs) -> Result<Vec<RandomWord>> {
+        // I was wrong. I thought this came down as an xml blob, but it doesn't. No, sir: this
+        // gets sent over the wire as a JSON array of escaped XML strings, for all have sinned
+        // and fall short of the glory of God. I can't imagine what anyone would want this for,
+        // but here it is.
+
+        // Dw, it's all good, not like we know when the strings are actually XML blobs in the API
+        // documentation anyway.
+
+        self.send(endpoint::random_words(args))
+    }
+
+    // get /words.json/reverseDictionary
+    pub fn reverse_dictionary(
+        &self,
+        args: &ReverseDictionaryArgs,
+    ) -> Result<DefinitionSearchResults> {
+        self.send(endpoint::reverse_dictionary(args))
+    }
+
+    // get /words.json/search/{query} (!! Deprecated for wordnik api v4 !!)
+    /// Searches for words matching a pattern, e.g. `"b*t"`.
+    ///
+    /// Wordnik has deprecated this endpoint, so it may stop working without notice; it's only
+    /// available with the `deprecated-endpoints` feature.
+    #[cfg(feature = "deprecated-endpoints")]
+    pub fn search_words(&self, query: &str, args: &SearchArgs) -> Result<WordSearchResults> {
+        self.send(endpoint::search_words(query, args))
+    }
+
+    // get /words.json/wordOfTheDay
+    /// Fetches the word of the day for the given date, or for today if there isn't one.
+    pub fn word_of_the_day(&self, date: Option<Date>) -> Result<WordOfTheDay> {
+        self.send(endpoint::word_of_the_day(date))
+    }
+
+    /// Iterates backwards through the word of the day archive, from `newest` to `oldest`.
+    pub fn word_of_the_day_archive(&self, newest: Date, oldest: Date) -> WordsOfTheDay<'_> {
+        WordsOfTheDay::new(self, newest, oldest)
+    }
+
+    // Word List API endpoint //
+
+    // get /wordList.json/{permalink}
+    pub fn word_list(&self, permalink: &str) -> Result<WordList> {
+        self.send(endpoint::word_list(permalink))
+    }
+
+    // get /wordList.json/{permalink}/words
+    pub fn word_list_words(
+        &self,
+        permalink: &str,
+        args: &WordListWordsArgs,
+    ) -> Result<Vec<WordListWord>> {
+        self.send(endpoint::word_list_words(permalink, args))
+    }
+
+    /// Iterates over every word in a word list, fetching pages of `args.limit` words as needed.
+    pub fn word_list_words_iter(
+        &self,
+        permalink: &str,
+        args: &WordListWordsArgs,
+    ) -> WordListWords<'_> {
+        WordListWords::new(self, permalink, args)
+    }
+
+    // The remaining word list endpoints act on behalf of a user, and need an auth token.
+
+    // post /wordLists.json
+    pub fn create_word_list(&self, list: &WordListMetadata) -> Result<WordList> {
+        self.send(endpoint::create_word_list(list))
+    }
+
+    // put /wordList.json/{permalink}
+    pub fn update_word_list(&self, permalink: &str, list: &WordListMetadata) -> Result<()> {
+        self.send_empty(endpoint::update_word_list(permalink, list))
+    }
+
+    // delete /wordList.json/{permalink}
+    pub fn delete_word_list(&self, permalink: &str) -> Result<()> {
+        self.send_empty(endpoint::delete_word_list(permalink))
+    }
+
+    // post /wordList.json/{permalink}/words
+    pub fn add_word_list_words(&self, permalink: &str, words: &[&str]) -> Result<()> {
+        self.send_empty(endpoint::add_word_list_words(permalink, words))
+    }
+
+    // post /wordList.json/{permalink}/deleteWords
+    pub fn delete_word_list_words(&self, permalink: &str, words: &[&str]) -> Result<()> {
+        self.send_empty(endpoint::delete_word_list_words(permalink, words))
+    }
+}
+
+// Calls `call` for every word on up to `concurrency` threads, each taking the next word as soon
+// as it's done with the last, and returns the results in the order of the words.
+fn fan_out<W, T, F>(words: &[W], concurrency: usize, call: F) -> Vec<Result<T>>
+where
+    W: AsRef<str> + Sync,
+    T: Send,
+    F: Fn(&str) -> Result<T> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let workers = concurrency.clamp(1, words.len().max(1));
+
+    let mut results: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        match words.get(idx) {
+                            Some(word) => results.push((idx, call(word.as_ref()))),
+                            None => return results,
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    results.sort_by_key(|&(idx, _)| idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
@@ -0,0 +1,148 @@
+use std::{
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::error::Error;
+
+/// A calendar date, as used by the word of the day endpoint.
+///
+/// This is only as much of a date type as the api needs; convert from whatever your own date
+/// library uses with `Date::new`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Returns `None` if the month or day is out of range.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if month == 0 || month > 12 || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+
+    /// Today's date in UTC, which is what the api uses.
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Self::from_days((seconds / 86_400) as i64)
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    pub fn month(self) -> u32 {
+        self.month
+    }
+
+    pub fn day(self) -> u32 {
+        self.day
+    }
+
+    /// The day before.
+    pub fn pred(self) -> Self {
+        Self::from_days(self.to_days() - 1)
+    }
+
+    /// The day after.
+    pub fn succ(self) -> Self {
+        Self::from_days(self.to_days() + 1)
+    }
+
+    // Days since 1970-01-01, per Howard Hinnant's days_from_civil.
+    pub(crate) fn to_days(self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    pub(crate) fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+}
+
+/// Formats as `yyyy-MM-dd`, which is what the api expects.
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = Error;
+
+    /// Parses `yyyy-MM-dd`, ignoring anything after the day (such as the time in the api's
+    /// publish dates).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.get(..10).unwrap_or(s).splitn(3, '-');
+        let mut next = || parts.next().and_then(|part| part.parse::<u32>().ok());
+        match (next(), next(), next()) {
+            (Some(year), Some(month), Some(day)) => Date::new(year as i32, month, day),
+            _ => None,
+        }
+        .ok_or(Error::Api("invalid date; expected yyyy-MM-dd"))
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Date;
+
+    #[test]
+    fn days_round_trip() {
+        let date = Date::new(2000, 2, 29).unwrap();
+        assert_eq!(Date::from_days(date.to_days()), date);
+        assert_eq!(Date::new(1970, 1, 1).unwrap().to_days(), 0);
+    }
+
+    #[test]
+    fn pred_crosses_month_and_year() {
+        let date = Date::new(2021, 1, 1).unwrap();
+        assert_eq!(date.pred(), Date::new(2020, 12, 31).unwrap());
+        assert_eq!(
+            Date::new(2020, 3, 1).unwrap().pred().to_string(),
+            "2020-02-29"
+        );
+        assert_eq!(date.pred().succ(), date);
+    }
+
+    #[test]
+    fn parses_api_dates() {
+        let date: Date = "2021-09-25T03:00:00.000Z".parse().unwrap();
+        assert_eq!(date, Date::new(2021, 9, 25).unwrap());
+        assert!("2021-02-30".parse::<Date>().is_err());
+        assert!("yesterday".parse::<Date>().is_err());
+    }
+}
@@ -0,0 +1,50 @@
+//! Percent-encoding for the parts of a url that come from the caller.
+
+use std::fmt;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+// Everything but the unreserved characters from RFC 3986. This is stricter than a path segment
+// or a query value strictly needs, but it's correct for both, and words can contain anything.
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Encodes a word, permalink or similar for use as a path segment or query value.
+pub(crate) fn component(value: &str) -> Component<'_> {
+    Component(value)
+}
+
+pub(crate) struct Component<'a>(&'a str);
+
+impl fmt::Display for Component<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // A segment of just dots would otherwise be read as "this directory" or "the parent".
+        if !self.0.is_empty() && self.0.bytes().all(|b| b == b'.') {
+            return self.0.chars().try_for_each(|_| f.write_str("%2E"));
+        }
+        utf8_percent_encode(self.0, COMPONENT).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::component;
+
+    #[test]
+    fn encodes_phrases_and_unicode() {
+        assert_eq!(component("ad hoc").to_string(), "ad%20hoc");
+        assert_eq!(component("naïve").to_string(), "na%C3%AFve");
+        assert_eq!(component("AC/DC").to_string(), "AC%2FDC");
+        assert_eq!(component("c#?&=").to_string(), "c%23%3F%26%3D");
+        assert_eq!(
+            component("well-known_x.y~z").to_string(),
+            "well-known_x.y~z"
+        );
+        assert_eq!(component("..").to_string(), "%2E%2E");
+        assert_eq!(component("...").to_string(), "%2E%2E%2E");
+        assert_eq!(component("a..").to_string(), "a..");
+    }
+}
@@ -0,0 +1,551 @@
+//! Everything about an api call that doesn't depend on how it's sent.
+//!
+//! Both clients build their requests from the functions here, so that the blocking and async
+//! versions of an endpoint can't drift apart.
+
+use std::time::SystemTime;
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, InvalidHeaderValue, CONTENT_TYPE},
+    Method,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "deprecated-endpoints")]
+use crate::args::SearchArgs;
+use crate::{
+    args::{
+        Args, AudioArgs, DefinitionsArgs, ExamplesArgs, FrequencyArgs, HyphenationArgs,
+        PhrasesArgs, PronunciationsArgs, RandomWordArgs, RandomWordsArgs, RelatedWordsArgs,
+        ReverseDictionaryArgs, WordListWordsArgs,
+    },
+    cache::{Cache, Endpoint, Slot},
+    date::Date,
+    encode,
+    error::Error,
+    model::WordListMetadata,
+    transport::{HttpRequest, HttpResponse},
+    ApiKey, Result,
+};
+
+pub(crate) static API_KEY: &str = "api_key";
+pub(crate) static AUTH_TOKEN: &str = "auth_token";
+pub(crate) static AUTH_TOKEN_REQUIRED: &str = "an auth token is required; see Client::authenticate";
+
+pub(crate) enum Body {
+    Json(serde_json::Value),
+    Text(String),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Auth {
+    None,
+    // Sent if the client has an auth token; public word lists don't need one.
+    Optional,
+    Required,
+}
+
+// Whether a response can be cached, and for which endpoint's ttl. See `cache::Cache`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Caching {
+    Never,
+    For(Endpoint),
+    // Until midnight UTC at the latest, when the answer changes.
+    UntilMidnight(Endpoint),
+}
+
+pub(crate) struct Request {
+    pub method: Method,
+    // Already percent-encoded; query values are encoded when the url is built.
+    pub path: String,
+    pub query: Vec<(&'static str, String)>,
+    pub body: Option<Body>,
+    pub auth: Auth,
+    pub caching: Caching,
+}
+
+impl Request {
+    fn new(method: Method, path: String) -> Self {
+        Self {
+            method,
+            path,
+            query: Vec::new(),
+            body: None,
+            auth: Auth::None,
+            caching: Caching::Never,
+        }
+    }
+
+    fn get(path: String) -> Self {
+        Self::new(Method::GET, path)
+    }
+
+    fn post(path: String) -> Self {
+        Self::new(Method::POST, path)
+    }
+
+    fn put(path: String) -> Self {
+        Self::new(Method::PUT, path)
+    }
+
+    fn delete(path: String) -> Self {
+        Self::new(Method::DELETE, path)
+    }
+
+    fn args<A>(mut self, args: &A) -> Self
+    where
+        for<'a> A: Args<'a>,
+    {
+        self.query.extend(args.args());
+        self
+    }
+
+    fn param(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.query.push((key, value.into()));
+        self
+    }
+
+    fn json(mut self, body: &impl Serialize) -> Self {
+        // None of our request bodies can fail to serialize.
+        self.body = Some(Body::Json(serde_json::to_value(body).unwrap()));
+        self
+    }
+
+    fn text(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(Body::Text(body.into()));
+        self
+    }
+
+    fn auth(mut self, auth: Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    fn cached(mut self, endpoint: Endpoint) -> Self {
+        self.caching = Caching::For(endpoint);
+        self
+    }
+
+    fn cached_until_midnight(mut self, endpoint: Endpoint) -> Self {
+        self.caching = Caching::UntilMidnight(endpoint);
+        self
+    }
+
+    /// Identifies the response for caching: the url, but with the query sorted, so that the
+    /// order args were given in doesn't matter.
+    pub fn cache_key(&self, base_url: &str) -> String {
+        let mut query: Vec<_> = self.query.iter().collect();
+        query.sort();
+
+        let mut key = format!("{}{}", base_url, self.path);
+        for (idx, (name, value)) in query.into_iter().enumerate() {
+            key += if idx == 0 { "?" } else { "&" };
+            key += name;
+            key += "=";
+            key += &encode::component(value).to_string();
+        }
+        key
+    }
+
+    pub fn url(&self, base_url: &str) -> String {
+        let mut url = format!("{}{}", base_url, self.path);
+        for (idx, (key, value)) in self.query.iter().enumerate() {
+            url += if idx == 0 { "?" } else { "&" };
+            url += key;
+            url += "=";
+            url += &encode::component(value).to_string();
+        }
+        url
+    }
+
+    /// Turns the request into what actually goes over the wire, adding the api key and, if the
+    /// endpoint wants one, the auth token.
+    ///
+    /// Both go in headers rather than the url, since urls have a way of turning up in logs and
+    /// error messages.
+    pub fn into_http(
+        self,
+        base_url: &str,
+        api_key: &ApiKey,
+        auth_token: Option<&str>,
+        headers: &HeaderMap,
+    ) -> Result<HttpRequest> {
+        if self.path.split('/').any(is_dot_segment) {
+            return Err(Error::Api(
+                "\".\" and \"..\" can't be sent as part of a url",
+            ));
+        }
+
+        let url = self.url(base_url);
+        let mut headers = headers.clone();
+        headers.insert(
+            HeaderName::from_static(API_KEY),
+            secret_header(api_key.expose()).map_err(|_| Error::Api("invalid api key"))?,
+        );
+
+        match (self.auth, auth_token) {
+            (Auth::Optional, Some(auth_token)) | (Auth::Required, Some(auth_token)) => {
+                let auth_token =
+                    secret_header(auth_token).map_err(|_| Error::Api("invalid auth token"))?;
+                headers.insert(HeaderName::from_static(AUTH_TOKEN), auth_token);
+            }
+            (Auth::Required, None) => return Err(Error::Api(AUTH_TOKEN_REQUIRED)),
+            _ => (),
+        }
+
+        let body = match self.body {
+            Some(Body::Json(body)) => {
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                Some(body.to_string().into_bytes())
+            }
+            Some(Body::Text(body)) => Some(body.into_bytes()),
+            None => None,
+        };
+
+        Ok(HttpRequest {
+            method: self.method,
+            url,
+            headers,
+            body,
+        })
+    }
+}
+
+pub(crate) enum Prepared<'a> {
+    Cached(HttpResponse),
+    // To be sent, and its response kept in the cache if there's a slot for it.
+    Send(HttpRequest, Option<(&'a Cache, Slot)>),
+}
+
+/// Answers `request` from the cache if it can, and otherwise turns it into what's to be sent.
+/// Both clients go through here and `keep`, so that they cache the same responses the same way;
+/// all they do themselves is send.
+pub(crate) fn prepare<'a>(
+    request: Request,
+    cache: Option<&'a Cache>,
+    base_url: &str,
+    api_key: &ApiKey,
+    auth_token: Option<&str>,
+    headers: &HeaderMap,
+    now: SystemTime,
+) -> Result<Prepared<'a>> {
+    let cached = cache.and_then(|cache| {
+        let slot = cache.slot(&request, base_url, auth_token.is_some(), now)?;
+        Some((cache, slot))
+    });
+    if let Some((cache, slot)) = &cached {
+        if let Some(response) = cache.get(slot, now) {
+            return Ok(Prepared::Cached(response));
+        }
+    }
+
+    let request = request.into_http(base_url, api_key, auth_token, headers)?;
+    Ok(Prepared::Send(request, cached))
+}
+
+/// Keeps the response to a request from `prepare` in the cache, if there's a slot for it.
+pub(crate) fn keep(slot: Option<(&Cache, Slot)>, response: &HttpResponse) {
+    if let Some((cache, slot)) = slot {
+        cache.insert(slot, response);
+    }
+}
+
+// Sensitive header values are left out of reqwest's debug output.
+fn secret_header(value: &str) -> Result<HeaderValue, InvalidHeaderValue> {
+    let mut value = HeaderValue::from_str(value)?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+// Whether a segment of an encoded path is "." or "..", which http clients resolve away even
+// when they're percent-encoded.
+fn is_dot_segment(segment: &str) -> bool {
+    matches!(
+        segment.to_ascii_lowercase().as_str(),
+        "." | ".." | "%2e" | ".%2e" | "%2e." | "%2e%2e"
+    )
+}
+
+/// A plain get for a url that isn't part of the api, e.g. an audio file.
+pub(crate) fn download(url: &str, headers: &HeaderMap) -> HttpRequest {
+    HttpRequest {
+        method: Method::GET,
+        url: url.into(),
+        headers: headers.clone(),
+        body: None,
+    }
+}
+
+pub(crate) fn check(response: HttpResponse) -> Result<HttpResponse> {
+    if response.is_success() {
+        Ok(response)
+    } else {
+        Err(Error::Status(response.status))
+    }
+}
+
+pub(crate) fn decode<T: DeserializeOwned>(response: HttpResponse) -> Result<T> {
+    Ok(serde_json::from_slice(&check(response)?.body)?)
+}
+
+// Account API endpoint //
+
+pub(crate) fn authenticate(username: &str, password: &str) -> Request {
+    // The password goes in the body so that it doesn't end up in anybody's access logs.
+    Request::post(format!(
+        "/account.json/authenticate/{}",
+        encode::component(username)
+    ))
+    .text(password)
+}
+
+pub(crate) fn api_token_status() -> Request {
+    Request::get("/account.json/apiTokenStatus".into())
+}
+
+// Word API endpoint //
+
+pub(crate) fn audio(word: &str, args: &AudioArgs) -> Request {
+    Request::get(format!("/word.json/{}/audio", encode::component(word))).args(args)
+}
+
+pub(crate) fn definitions(word: &str, args: &DefinitionsArgs) -> Request {
+    Request::get(format!(
+        "/word.json/{}/definitions",
+        encode::component(word)
+    ))
+    .args(args)
+    .cached(Endpoint::Definitions)
+}
+
+pub(crate) fn etymologies(word: &str) -> Request {
+    Request::get(format!(
+        "/word.json/{}/etymologies",
+        encode::component(word)
+    ))
+    .cached(Endpoint::Etymologies)
+}
+
+pub(crate) fn examples(word: &str, args: &ExamplesArgs) -> Request {
+    Request::get(format!("/word.json/{}/examples", encode::component(word)))
+        .args(args)
+        .cached(Endpoint::Examples)
+}
+
+pub(crate) fn frequency(word: &str, args: &FrequencyArgs) -> Request {
+    Request::get(format!("/word.json/{}/frequency", encode::component(word)))
+        .args(args)
+        .cached(Endpoint::Frequency)
+}
+
+pub(crate) fn hyphenation(word: &str, args: &HyphenationArgs) -> Request {
+    Request::get(format!(
+        "/word.json/{}/hyphenation",
+        encode::component(word)
+    ))
+    .args(args)
+    .cached(Endpoint::Hyphenation)
+}
+
+pub(crate) fn phrases(word: &str, args: &PhrasesArgs) -> Request {
+    Request::get(format!("/word.json/{}/phrases", encode::component(word)))
+        .args(args)
+        .cached(Endpoint::Phrases)
+}
+
+pub(crate) fn pronunciations(word: &str, args: &PronunciationsArgs) -> Request {
+    Request::get(format!(
+        "/word.json/{}/pronunciations",
+        encode::component(word)
+    ))
+    .args(args)
+    .cached(Endpoint::Pronunciations)
+}
+
+pub(crate) fn related_words(word: &str, args: &RelatedWordsArgs) -> Request {
+    Request::get(format!(
+        "/word.json/{}/relatedWords",
+        encode::component(word)
+    ))
+    .args(args)
+    .cached(Endpoint::RelatedWords)
+}
+
+pub(crate) fn scrabble_score(word: &str) -> Request {
+    Request::get(format!(
+        "/word.json/{}/scrabbleScore",
+        encode::component(word)
+    ))
+    .cached(Endpoint::ScrabbleScore)
+}
+
+pub(crate) fn top_example(word: &str, use_canonical: bool) -> Request {
+    let request = Request::get(format!("/word.json/{}/topExample", encode::component(word)))
+        .cached(Endpoint::TopExample);
+    if use_canonical {
+        request.param("useCanonical", "true")
+    } else {
+        request
+    }
+}
+
+// Words API endpoint //
+
+pub(crate) fn random_word(args: &RandomWordArgs) -> Request {
+    Request::get("/words.json/randomWord".into()).args(args)
+}
+
+pub(crate) fn random_words(args: &RandomWordsArgs) -> Request {
+    Request::get("/words.json/randomWords".into()).args(args)
+}
+
+pub(crate) fn reverse_dictionary(args: &ReverseDictionaryArgs) -> Request {
+    Request::get("/words.json/reverseDictionary".into())
+        .args(args)
+        .cached(Endpoint::ReverseDictionary)
+}
+
+#[cfg(feature = "deprecated-endpoints")]
+pub(crate) fn search_words(query: &str, args: &SearchArgs) -> Request {
+    Request::get(format!("/words.json/search/{}", encode::component(query)))
+        .args(args)
+        .cached(Endpoint::SearchWords)
+}
+
+pub(crate) fn word_of_the_day(date: Option<Date>) -> Request {
+    let request = Request::get("/words.json/wordOfTheDay".into());
+    match date {
+        Some(date) => request
+            .param("date", date.to_string())
+            .cached(Endpoint::WordOfTheDay),
+        None => request.cached_until_midnight(Endpoint::WordOfTheDay),
+    }
+}
+
+// Word List API endpoint //
+
+pub(crate) fn word_list(permalink: &str) -> Request {
+    Request::get(format!("/wordList.json/{}", encode::component(permalink)))
+        .auth(Auth::Optional)
+        .cached(Endpoint::WordList)
+}
+
+pub(crate) fn word_list_words(permalink: &str, args: &WordListWordsArgs) -> Request {
+    Request::get(format!(
+        "/wordList.json/{}/words",
+        encode::component(permalink)
+    ))
+    .args(args)
+    .auth(Auth::Optional)
+    .cached(Endpoint::WordListWords)
+}
+
+pub(crate) fn create_word_list(list: &WordListMetadata) -> Request {
+    Request::post("/wordLists.json".into())
+        .json(list)
+        .auth(Auth::Required)
+}
+
+pub(crate) fn update_word_list(permalink: &str, list: &WordListMetadata) -> Request {
+    Request::put(format!("/wordList.json/{}", encode::component(permalink)))
+        .json(list)
+        .auth(Auth::Required)
+}
+
+pub(crate) fn delete_word_list(permalink: &str) -> Request {
+    Request::delete(format!("/wordList.json/{}", encode::component(permalink))).auth(Auth::Required)
+}
+
+pub(crate) fn add_word_list_words(permalink: &str, words: &[&str]) -> Request {
+    Request::post(format!(
+        "/wordList.json/{}/words",
+        encode::component(permalink)
+    ))
+    .json(&word_objects(words))
+    .auth(Auth::Required)
+}
+
+pub(crate) fn delete_word_list_words(permalink: &str, words: &[&str]) -> Request {
+    Request::post(format!(
+        "/wordList.json/{}/deleteWords",
+        encode::component(permalink)
+    ))
+    .json(&word_objects(words))
+    .auth(Auth::Required)
+}
+
+// The word list endpoints want `[{ "word": "..." }]` rather than a list of strings.
+fn word_objects(words: &[&str]) -> Vec<serde_json::Value> {
+    words
+        .iter()
+        .map(|word| serde_json::json!({ "word": word }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderMap;
+
+    use crate::args::{DefinitionsArgs, ReverseDictionaryArgs};
+
+    static BASE_URL: &str = "https://api.wordnik.com/v4";
+
+    #[test]
+    fn encodes_words_in_paths() {
+        let url = super::definitions("ad hoc", &DefinitionsArgs::new()).url(BASE_URL);
+        assert_eq!(
+            url,
+            "https://api.wordnik.com/v4/word.json/ad%20hoc/definitions"
+        );
+
+        let url = super::etymologies("naïve").url(BASE_URL);
+        assert_eq!(
+            url,
+            "https://api.wordnik.com/v4/word.json/na%C3%AFve/etymologies"
+        );
+
+        let url = super::word_list_words("a/b?c#d", &Default::default()).url(BASE_URL);
+        assert_eq!(
+            url,
+            "https://api.wordnik.com/v4/wordList.json/a%2Fb%3Fc%23d/words"
+        );
+
+        let url = super::definitions("..", &DefinitionsArgs::new()).url(BASE_URL);
+        assert_eq!(
+            url,
+            "https://api.wordnik.com/v4/word.json/%2E%2E/definitions"
+        );
+        // Even encoded, http clients would resolve that to /v4/definitions.
+        let request = super::definitions("..", &DefinitionsArgs::new()).into_http(
+            BASE_URL,
+            &"hunter2".into(),
+            None,
+            &HeaderMap::new(),
+        );
+        assert!(request.is_err());
+    }
+
+    #[test]
+    fn encodes_query_values() {
+        let args = ReverseDictionaryArgs::new("a café & a bar");
+        let url = super::reverse_dictionary(&args).url(BASE_URL);
+        assert_eq!(
+            url,
+            "https://api.wordnik.com/v4/words.json/reverseDictionary\
+             ?query=a%20caf%C3%A9%20%26%20a%20bar"
+        );
+    }
+
+    #[test]
+    fn sends_api_key_in_header() {
+        let request = super::etymologies("horse")
+            .into_http(BASE_URL, &"hunter2".into(), None, &HeaderMap::new())
+            .unwrap();
+        assert_eq!(
+            request.url,
+            "https://api.wordnik.com/v4/word.json/horse/etymologies"
+        );
+        assert_eq!(request.headers["api_key"], "hunter2");
+        assert!(!format!("{:?}", request).contains("hunter2"));
+    }
+}
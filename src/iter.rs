@@ -0,0 +1,150 @@
+use std::vec;
+
+use crate::{
+    args::{ExamplesArgs, WordListWordsArgs},
+    date::Date,
+    model::{Example, WordListWord, WordOfTheDay},
+    Client, Result,
+};
+
+/// Walks the examples for a word one page at a time, requesting each page only when the
+/// previous one has been used up.
+///
+/// Pages are `limit` examples long, starting from `skip`. Iteration ends with the first short
+/// page or the first error.
+pub struct Examples<'a> {
+    client: &'a Client,
+    word: String,
+    args: ExamplesArgs,
+    page: vec::IntoIter<Example>,
+    done: bool,
+}
+
+impl<'a> Examples<'a> {
+    pub(crate) fn new(client: &'a Client, word: &str, args: &ExamplesArgs) -> Self {
+        Self {
+            client,
+            word: word.into(),
+            args: args.clone(),
+            page: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Examples<'_> {
+    type Item = Result<Example>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(example) = self.page.next() {
+                return Some(Ok(example));
+            }
+
+            if self.done || self.args.limit == 0 {
+                return None;
+            }
+
+            let examples = match self.client.examples(&self.word, &self.args) {
+                Ok(results) => results.examples,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+
+            self.done = examples.len() < self.args.limit as usize;
+            self.args.skip += examples.len() as u32;
+            self.page = examples.into_iter();
+        }
+    }
+}
+
+/// Walks the words in a word list one page at a time, the same way as `Examples`.
+pub struct WordListWords<'a> {
+    client: &'a Client,
+    permalink: String,
+    args: WordListWordsArgs,
+    page: vec::IntoIter<WordListWord>,
+    done: bool,
+}
+
+impl<'a> WordListWords<'a> {
+    pub(crate) fn new(client: &'a Client, permalink: &str, args: &WordListWordsArgs) -> Self {
+        Self {
+            client,
+            permalink: permalink.into(),
+            args: args.clone(),
+            page: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+}
+
+impl Iterator for WordListWords<'_> {
+    type Item = Result<WordListWord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(word) = self.page.next() {
+                return Some(Ok(word));
+            }
+
+            if self.done || self.args.limit == 0 {
+                return None;
+            }
+
+            let words = match self.client.word_list_words(&self.permalink, &self.args) {
+                Ok(words) => words,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+
+            self.done = words.len() < self.args.limit as usize;
+            self.args.skip += words.len() as u32;
+            self.page = words.into_iter();
+        }
+    }
+}
+
+/// Walks the word of the day archive backwards, one day at a time, from the newest date to the
+/// oldest (inclusive).
+///
+/// Each day is requested separately and errors don't end iteration, so a backfill job can note
+/// the failed dates and carry on.
+pub struct WordsOfTheDay<'a> {
+    client: &'a Client,
+    next: Date,
+    oldest: Date,
+}
+
+impl<'a> WordsOfTheDay<'a> {
+    pub(crate) fn new(client: &'a Client, newest: Date, oldest: Date) -> Self {
+        Self {
+            client,
+            next: newest,
+            oldest,
+        }
+    }
+}
+
+impl Iterator for WordsOfTheDay<'_> {
+    type Item = (Date, Result<WordOfTheDay>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next < self.oldest {
+            return None;
+        }
+
+        let date = self.next;
+        self.next = date.pred();
+        Some((date, self.client.word_of_the_day(Some(date))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.next.to_days() - self.oldest.to_days() + 1).max(0) as usize;
+        (remaining, Some(remaining))
+    }
+}
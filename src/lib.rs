@@ -1,191 +1,201 @@
-mod args;
-mod error;
-mod model;
-
-use std::fmt;
-
-use args::{Args, DefinitionsArgs, RandomWordArgs, RandomWordsArgs};
-use model::{Definition, Etymology, RandomWord};
-
+mod api_key;
+pub mod args;
+#[cfg(feature = "async")]
+mod async_client;
+#[cfg(any(feature = "blocking", feature = "async"))]
+mod builder;
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub mod cache;
+#[cfg(feature = "blocking")]
+pub mod cassette;
+#[cfg(feature = "blocking")]
+mod client;
+pub mod date;
+mod encode;
+#[cfg(any(feature = "blocking", feature = "async"))]
+mod endpoint;
+pub mod error;
+#[cfg(feature = "blocking")]
+pub mod iter;
+pub mod model;
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub mod rate_limit;
+#[cfg(feature = "blocking")]
+pub mod retry;
+pub mod scrabble;
+#[cfg(all(test, any(feature = "blocking", feature = "async")))]
+mod test_server;
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub mod transport;
+
+pub use api_key::ApiKey;
+#[cfg(feature = "async")]
+pub use async_client::AsyncClient;
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub use builder::ClientBuilder;
+#[cfg(feature = "blocking")]
+pub use client::Client;
+
+#[cfg(any(feature = "blocking", feature = "async"))]
 static API_BASE: &str = "https://api.wordnik.com/v4";
+#[cfg(any(feature = "blocking", feature = "async"))]
 static USER_AGENT: &str = concat!("wordnik rust client v", env!("CARGO_PKG_VERSION"));
 
-#[cfg(test)]
-static WORDNIK_API_KEY: &str = "WORDNIK_API_KEY";
-
 pub type Result<T, E = error::Error> = std::result::Result<T, E>;
 
-#[derive(Clone)]
-pub struct Client {
-    inner: reqwest::blocking::Client,
-    api_key: String,
-}
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use crate::args::{
+        AudioArgs, DefinitionsArgs, ExamplesArgs, FrequencyArgs, HyphenationArgs, PartOfSpeech,
+        PhrasesArgs, PronunciationsArgs, RandomWordArgs, RandomWordsArgs, RelatedWordsArgs,
+        RelationshipType, ReverseDictionaryArgs, TypeFormat, WordListWordsArgs,
+    };
+    use crate::cache::{Cache, CacheStats, MemoryCache};
+    use crate::date::Date;
+    use crate::error::Error;
+    use crate::model::{WordListMetadata, WordListType};
+    use crate::rate_limit::RateLimit;
+    use crate::retry::{Clock, FakeClock, RetryPolicy};
+    use crate::test_server::{Reply, TestServer};
+    use crate::transport::FixtureTransport;
+    use std::sync::Arc;
+    use std::time::Duration;
 
-impl fmt::Debug for Client {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Client")
-            .field("inner", &self.inner)
-            .field("api_key", &"<api_key>")
-            .finish()
+    #[test]
+    fn can_request_api_token_status() {
+        let client = super::Client::test_client();
+        let status = dbg!(client.api_token_status()).unwrap();
+        assert!(status.valid);
     }
-}
 
-#[cfg(test)]
-impl Client {
-    fn test_client() -> Self {
-        dotenv::dotenv().ok();
-        Self {
-            inner: build_inner_client().unwrap(),
-            api_key: dotenv::var(WORDNIK_API_KEY).unwrap(),
-        }
+    #[test]
+    fn can_request_definition() {
+        let client = super::Client::test_client();
+        assert!(dbg!(client.definitions("fireplace")).is_ok())
     }
-}
 
-impl Client {
-    pub fn new(api_key: impl Into<String>) -> Self {
-        Self {
-            inner: build_inner_client().unwrap(),
-            api_key: api_key.into(),
-        }
+    #[test]
+    fn can_request_definition_with_args() {
+        let client = super::Client::test_client();
+        let mut args: DefinitionsArgs = DefinitionsArgs::new();
+        args.limit = 1;
+        let res = dbg!(client.definitions_args("test", &args));
+        assert!(res.is_ok() && res.unwrap().len() == 1);
     }
 
-    // Word API endpoint //
-
-    // get /word.json/{word}/audio
-
-    // get /word.json/{word}/definitions
-    pub fn definitions(&self, word: &str) -> Result<Vec<Definition>> {
-        let url = format!(
-            "{}/word.json/{}/definitions?api_key={}",
-            API_BASE, word, self.api_key
-        );
-        let request = self.inner.get(&url);
-        Ok(request.send()?.json()?)
+    #[test]
+    fn can_request_audio() {
+        let client = super::Client::test_client();
+        let mut args = AudioArgs::new();
+        args.limit = 2;
+        let res = dbg!(client.audio("fireplace", &args));
+        assert!(res.is_ok() && res.unwrap().len() <= 2);
     }
 
-    pub fn definitions_args(&self, word: &str, args: &DefinitionsArgs) -> Result<Vec<Definition>> {
-        let url = format!(
-            "{}/word.json/{}/definitions?api_key={}&{}",
-            API_BASE,
-            word,
-            self.api_key,
-            args.to_get_query_str()
-        );
-        let request = self.inner.get(&url);
-        Ok(dbg!(request.send()?).json()?)
+    #[test]
+    fn can_download_audio() {
+        let client = super::Client::test_client();
+        let files = client.audio("fireplace", &AudioArgs::new()).unwrap();
+        let bytes = client.download_audio(&files[0]).unwrap();
+        assert!(!bytes.is_empty());
     }
 
-    // get /word.json/{word}/etymologies
-    pub fn etymologies(&self, word: &str) -> Result<Vec<Etymology>> {
-        let url = format!(
-            "{}/word.json/{}/etymologies?api_key={}",
-            API_BASE, word, self.api_key
-        );
-        let request = self.inner.get(&url);
-        Ok(request.send()?.json()?)
-    }
-
-    // get /word.json/{word}/examples
-    // get /word.json/{word}/frequency
-    // get /word.json/{word}/hyphenation
-    // get /word.json/{word}/phrases
-    // get /word.json/{word}/pronunciations
-    // get /word.json/{word}/relatedWords
-    // get /word.json/{word}/scrabbleScore
-    // get /word.json/{word}/topExample
-
-    // Words API endpoint //
-
-    // get /words.json/randomWord
-    pub fn random_word(&self) -> Result<RandomWord> {
-        let url = format!(
-            "{}/words.json/randomWord?api_key={}",
-            API_BASE, self.api_key
-        );
-        let request = self.inner.get(&url);
-        Ok(request.send()?.json()?)
+    #[test]
+    fn can_request_examples() {
+        let client = super::Client::test_client();
+        let mut args = ExamplesArgs::new();
+        args.limit = 3;
+        let res = dbg!(client.examples("fireplace", &args));
+        assert!(res.is_ok() && res.unwrap().examples.len() <= 3);
     }
 
-    pub fn random_word_args(&self, args: &RandomWordArgs) -> Result<RandomWord> {
-        let url = format!(
-            "{}/words.json/randomWord?api_key={}&{}",
-            API_BASE,
-            self.api_key,
-            args.to_get_query_str()
-        );
-        let request = self.inner.get(&url);
-        Ok(request.send()?.json()?)
+    #[test]
+    fn can_iterate_examples_across_pages() {
+        let client = super::Client::test_client();
+        let mut args = ExamplesArgs::new();
+        args.limit = 2;
+        let examples: Vec<_> = client.examples_iter("fireplace", &args).take(5).collect();
+        assert_eq!(examples.len(), 5);
+        assert!(examples.iter().all(|example| example.is_ok()));
     }
 
-    // get /words.json/randomWords
-    pub fn random_words(&self) -> Result<Vec<RandomWord>> {
-        let url = format!(
-            "{}/words.json/randomWords?api_key={}",
-            API_BASE, self.api_key
-        );
-
-        Ok(self.inner.get(&url).send()?.json()?)
+    #[test]
+    fn can_request_frequency() {
+        let client = super::Client::test_client();
+        let mut args = FrequencyArgs::new();
+        args.start_year = 1990;
+        args.end_year = 2000;
+        let res = dbg!(client.frequency("fireplace", &args));
+        assert!(res.is_ok());
     }
 
-    pub fn random_words_args(&self, args: &RandomWordsArgs) -> Result<Vec<RandomWord>> {
-        let url = format!(
-            "{}/words.json/randomWords?api_key={}&{}",
-            API_BASE,
-            self.api_key,
-            args.to_get_query_str()
-        );
-
-        // I was wrong. I thought this came down as an xml blob, but it doesn't. No, sir: this
-        // gets sent over the wire as a JSON array of escaped XML strings, for all have sinned
-        // and fall short of the glory of God. I can't imagine what anyone would want this for,
-        // but here it is.
-
-        // Dw, it's all good, not like we know when the strings are actually XML blobs in the API
-        // documentation anyway.
-
-        Ok(self.inner.get(&url).send()?.json()?)
+    #[test]
+    fn can_request_hyphenation() {
+        let client = super::Client::test_client();
+        let res = dbg!(client.hyphenation("fireplace", &HyphenationArgs::new()));
+        assert!(res.is_ok() && !res.unwrap().syllables.is_empty());
     }
 
-    // get /words.json/reverseDictionary
-    // get /words.json/search/{query} (!! Deprecated for wordnik api v4 !!)
-    // get /words.json/wordOfTheDay
-}
-
-#[inline]
-fn build_inner_client() -> reqwest::Result<reqwest::blocking::Client> {
-    use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
+    #[test]
+    fn can_request_phrases() {
+        let client = super::Client::test_client();
+        let mut args = PhrasesArgs::new();
+        args.limit = 3;
+        let res = dbg!(client.phrases("fireplace", &args));
+        assert!(res.is_ok() && res.unwrap().len() <= 3);
+    }
 
-    let mut headers = HeaderMap::new();
-    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
-    reqwest::blocking::Client::builder()
-        .default_headers(headers)
-        .user_agent(USER_AGENT)
-        .build()
-}
+    #[test]
+    fn can_request_ipa_pronunciations() {
+        let client = super::Client::test_client();
+        let mut args = PronunciationsArgs::new();
+        args.type_format = Some(TypeFormat::Ipa);
+        let res = dbg!(client.pronunciations("fireplace", &args));
+        assert!(res.is_ok());
+        assert!(res
+            .unwrap()
+            .iter()
+            .all(|pron| pron.raw_type.as_deref() == Some("IPA")));
+    }
 
-#[cfg(test)]
-mod tests {
-    use crate::args::{DefinitionsArgs, PartOfSpeech, RandomWordArgs, RandomWordsArgs};
+    #[test]
+    fn can_request_related_words() {
+        let client = super::Client::test_client();
+        let mut args = RelatedWordsArgs::new();
+        args.relationship_types.push(RelationshipType::Synonym);
+        let res = dbg!(client.related_words("fireplace", &args));
+        assert!(res.is_ok());
+        assert!(res
+            .unwrap()
+            .relationship_types()
+            .all(|relationship_type| relationship_type == RelationshipType::Synonym));
+    }
 
     #[test]
-    fn can_create_test_client() {
+    fn can_request_scrabble_score() {
         let client = super::Client::test_client();
-        assert!(!client.api_key.is_empty());
+        let res = dbg!(client.scrabble_score("fireplace"));
+        assert!(res.is_ok() && res.unwrap() > 0);
     }
 
+    // Only worth running against the real api, which the synthetic cassette can't stand in for.
     #[test]
-    fn can_request_definition() {
+    #[ignore = "needs the real api or a recording of it; see README.md"]
+    fn scrabble_score_agrees_with_offline_scorer() {
         let client = super::Client::test_client();
-        assert!(dbg!(client.definitions("fireplace")).is_ok())
+
+        // The words from the fixtures in /resource
+        for word in ["fireplace", "horse", "yeti"] {
+            let score = client.scrabble_score(word).unwrap();
+            assert_eq!(score as u32, crate::scrabble::score(word), "{}", word);
+        }
     }
 
     #[test]
-    fn can_request_definition_with_args() {
+    fn can_request_top_example() {
         let client = super::Client::test_client();
-        let mut args: DefinitionsArgs = DefinitionsArgs::new();
-        args.limit = 1;
-        let res = dbg!(client.definitions_args("test", &args));
-        assert!(res.is_ok() && res.unwrap().len() == 1);
+        let res = dbg!(client.top_example("fireplace", false));
+        assert!(res.is_ok() && !res.unwrap().text.is_empty());
     }
 
     #[test]
@@ -220,9 +230,480 @@ mod tests {
         assert!(res.is_ok() && res.unwrap().len() == 3);
     }
 
+    #[test]
+    fn can_request_reverse_dictionary() {
+        let client = super::Client::test_client();
+        let mut args = ReverseDictionaryArgs::new("recess for holding a fire");
+        args.include_part_of_speech.push(PartOfSpeech::Noun);
+        args.limit = 5;
+        let res = dbg!(client.reverse_dictionary(&args)).unwrap();
+        assert!(res.results.len() <= 5 && res.total_results >= res.results.len() as i64);
+    }
+
+    #[test]
+    fn can_request_word_of_the_day() {
+        let client = super::Client::test_client();
+        let date = Date::new(2021, 9, 25).unwrap();
+        let res = dbg!(client.word_of_the_day(Some(date))).unwrap();
+        let published: Date = res.publish_date.unwrap().parse().unwrap();
+        assert_eq!(published, date);
+    }
+
+    #[test]
+    fn can_walk_word_of_the_day_archive() {
+        let client = super::Client::test_client();
+        let newest = Date::new(2021, 3, 1).unwrap();
+        let oldest = Date::new(2021, 2, 27).unwrap();
+        let days: Vec<_> = client
+            .word_of_the_day_archive(newest, oldest)
+            .map(|(date, res)| (date.to_string(), res.is_ok()))
+            .collect();
+        assert_eq!(
+            days,
+            [
+                ("2021-03-01".to_string(), true),
+                ("2021-02-28".to_string(), true),
+                ("2021-02-27".to_string(), true),
+            ]
+        );
+    }
+
+    #[cfg(feature = "deprecated-endpoints")]
+    #[test]
+    fn can_search_words() {
+        let client = super::Client::test_client();
+        let mut args = crate::args::SearchArgs::new();
+        args.min_length = 3;
+        args.max_length = 3;
+        args.limit = 5;
+        let res = dbg!(client.search_words("b*t", &args)).unwrap();
+        assert!(res.search_results.len() <= 5);
+        assert!(res
+            .search_results
+            .iter()
+            .all(|result| result.word.len() == 3));
+    }
+
+    #[test]
+    fn can_request_word_list() {
+        let client = super::Client::test_client();
+        let list = dbg!(client.word_list("sat-words")).unwrap();
+        assert_eq!(list.permalink, "sat-words");
+    }
+
+    #[test]
+    fn can_iterate_word_list_words() {
+        let client = super::Client::test_client();
+        let mut args = WordListWordsArgs::new();
+        args.limit = 2;
+        let words: Vec<_> = client
+            .word_list_words_iter("sat-words", &args)
+            .take(5)
+            .collect();
+        assert_eq!(words.len(), 5);
+        assert!(words.iter().all(|word| word.is_ok()));
+    }
+
     #[test]
     fn can_request_etymology() {
         let client = super::Client::test_client();
         assert!(dbg!(client.etymologies("horse")).is_ok());
     }
+
+    #[test]
+    fn can_authenticate_against_stub() {
+        let server = TestServer::start(vec![Reply::json(
+            r#"{ "token": "user-token", "userId": 42, "userSignature": "sig" }"#,
+        )]);
+        let client = super::Client::stub_client(&server.base_url);
+
+        let token = client.authenticate("someone", "hunter2").unwrap();
+        assert_eq!(token.token, "user-token");
+        assert!(!format!("{:?}", token).contains("user-token"));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/account.json/authenticate/someone");
+        assert_eq!(requests[0].body, "hunter2");
+        assert_eq!(requests[0].headers["api_key"], "stub_api_key");
+    }
+
+    #[test]
+    fn can_manage_word_list_against_stub() {
+        let server = TestServer::start(vec![
+            Reply::json(
+                r#"{ "id": 1, "permalink": "my-list", "name": "My list", "type": "PRIVATE" }"#,
+            ),
+            Reply::status(200),
+            Reply::status(200),
+            Reply::status(200),
+            Reply::status(200),
+        ]);
+        let client = super::Client::stub_client(&server.base_url).with_auth_token("user-token");
+
+        let mut metadata = WordListMetadata::new("My list", WordListType::Private);
+        let list = client.create_word_list(&metadata).unwrap();
+        assert_eq!(list.permalink, "my-list");
+
+        client
+            .add_word_list_words("my-list", &["hearth", "ingle"])
+            .unwrap();
+        client
+            .delete_word_list_words("my-list", &["ingle"])
+            .unwrap();
+        metadata.description = Some("Words about fireplaces".into());
+        client.update_word_list("my-list", &metadata).unwrap();
+        client.delete_word_list("my-list").unwrap();
+
+        let requests = server.requests();
+        let summary: Vec<_> = requests
+            .iter()
+            .map(|request| (request.method.as_str(), request.path.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("POST", "/wordLists.json"),
+                ("POST", "/wordList.json/my-list/words"),
+                ("POST", "/wordList.json/my-list/deleteWords"),
+                ("PUT", "/wordList.json/my-list"),
+                ("DELETE", "/wordList.json/my-list"),
+            ]
+        );
+        assert!(requests
+            .iter()
+            .all(|request| request.headers["auth_token"] == "user-token"));
+        let bodies: Vec<serde_json::Value> = requests
+            .iter()
+            .map(|request| serde_json::from_str(&request.body).unwrap_or_default())
+            .collect();
+        assert_eq!(
+            bodies[0],
+            serde_json::json!({ "name": "My list", "type": "PRIVATE" })
+        );
+        assert_eq!(
+            bodies[1],
+            serde_json::json!([{ "word": "hearth" }, { "word": "ingle" }])
+        );
+        assert_eq!(
+            bodies[3],
+            serde_json::json!({
+                "name": "My list",
+                "description": "Words about fireplaces",
+                "type": "PRIVATE",
+            })
+        );
+    }
+
+    #[test]
+    fn word_list_management_requires_auth_token() {
+        let client = super::Client::stub_client("http://127.0.0.1:9");
+        assert!(client.delete_word_list("my-list").is_err());
+    }
+
+    fn fixture_client() -> super::Client {
+        let transport = FixtureTransport::new()
+            .with_file("/word.json/fireplace/definitions", "resource/response.json")
+            .unwrap()
+            .with_file("/word.json/horse/etymologies", "resource/etymologies.json")
+            .unwrap();
+        super::Client::builder("fixture_api_key")
+            .transport(transport)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn can_read_definitions_from_fixture() {
+        let definitions = fixture_client().definitions("fireplace").unwrap();
+        assert!(definitions.iter().all(|d| d.word == "fireplace"));
+        assert_eq!(definitions[0].source_dictionary.as_deref(), Some("ahd-5"));
+        assert!(definitions.iter().any(|d| d
+            .related_words
+            .iter()
+            .any(|r| r.relationship_type == "synonym")));
+        assert!(definitions.iter().any(|d| !d.example_uses.is_empty()));
+    }
+
+    #[test]
+    fn looks_up_many_words_in_order() {
+        let transport = Arc::new(
+            FixtureTransport::new()
+                .with_file("/word.json/fireplace/definitions", "resource/response.json")
+                .unwrap()
+                .with_body("/word.json/hearth/definitions", r#"[{ "word": "hearth" }]"#),
+        );
+        let client = super::Client::builder("fixture_api_key")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+
+        let words = ["fireplace", "cart", "hearth", "fireplace", "horse"];
+        let res = client.definitions_many(&words, &DefinitionsArgs::new(), 3);
+        assert_eq!(res.len(), words.len());
+        assert_eq!(transport.requests().len(), words.len());
+        for (word, res) in words.iter().zip(&res) {
+            match res {
+                Ok(definitions) => assert!(definitions.iter().all(|d| d.word == *word)),
+                Err(e) => assert!(matches!(e, Error::Status(404)), "{}", word),
+            }
+        }
+        assert_eq!(
+            res.iter().map(Result::is_ok).collect::<Vec<_>>(),
+            [true, false, true, true, false]
+        );
+        assert!(client.etymologies_many(&Vec::<String>::new(), 0).is_empty());
+    }
+
+    #[test]
+    fn many_word_lookups_share_the_rate_limit() {
+        let client = super::Client::builder("fixture_api_key")
+            .transport(FixtureTransport::new().with_body("/etymologies", "[]"))
+            .rate_limit(RateLimit::per_minute(2).fail_fast())
+            .clock(FakeClock::default())
+            .build()
+            .unwrap();
+
+        let res = client.etymologies_many(&["horse", "cart", "hearth", "ingle"], 4);
+        assert_eq!(res.iter().filter(|res| res.is_ok()).count(), 2);
+        assert_eq!(
+            res.iter()
+                .filter(|res| matches!(res, Err(Error::RateLimited(_))))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn can_read_etymologies_from_fixture() {
+        let etymologies = fixture_client().etymologies("horse").unwrap();
+        assert!(etymologies[0].etymology.contains("horsion"));
+    }
+
+    #[test]
+    fn fixture_transport_answers_unknown_paths_with_404() {
+        let res = fixture_client().etymologies("cart");
+        assert!(matches!(res, Err(Error::Status(404))));
+    }
+
+    #[test]
+    fn fixture_transport_records_requests() {
+        let transport = Arc::new(FixtureTransport::new());
+        let client = super::Client::builder("fixture_api_key")
+            .auth_token("user-token")
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+        client.add_word_list_words("my-list", &["hearth"]).ok();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path(), "/v4/wordList.json/my-list/words");
+        assert_eq!(requests[0].headers["auth_token"], "user-token");
+        assert_eq!(requests[0].headers["content-type"], "application/json");
+        assert_eq!(
+            requests[0].body.as_deref(),
+            Some(&br#"[{"word":"hearth"}]"#[..])
+        );
+    }
+
+    #[test]
+    fn sends_encoded_words_to_stub() {
+        let server = TestServer::start(vec![Reply::json("[]")]);
+        let client = super::Client::stub_client(&server.base_url);
+        client.definitions("ad hoc").unwrap();
+
+        assert_eq!(server.requests()[0].path, "/word.json/ad%20hoc/definitions");
+    }
+
+    #[test]
+    fn fixture_transport_matches_decoded_paths() {
+        let transport = FixtureTransport::new().with_body("/word.json/naïve/etymologies", "[]");
+        let client = super::Client::builder("fixture_api_key")
+            .transport(transport)
+            .build()
+            .unwrap();
+        assert!(client.etymologies("naïve").unwrap().is_empty());
+    }
+
+    #[test]
+    fn retries_transient_failures_against_stub() {
+        let server = TestServer::start(vec![
+            Reply::status(503),
+            Reply::status(429).header("Retry-After", "7"),
+            Reply::json(r#"["<ety>[AS. <ets>horsion</ets>.]</ety>"]"#),
+        ]);
+        let clock = Arc::new(FakeClock::default());
+        let client = super::Client::builder("stub_api_key")
+            .base_url(&server.base_url)
+            .retry_policy(
+                RetryPolicy::new()
+                    .base_delay(Duration::from_millis(100))
+                    .jitter(0.0),
+            )
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(client.etymologies("horse").unwrap().len(), 1);
+        assert_eq!(server.requests().len(), 3);
+        assert_eq!(
+            *clock.slept.lock().unwrap(),
+            [Duration::from_millis(100), Duration::from_secs(7)]
+        );
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let server = TestServer::start(vec![Reply::status(500), Reply::status(500)]);
+        let client = super::Client::builder("stub_api_key")
+            .base_url(&server.base_url)
+            .retry_policy(RetryPolicy::new().max_attempts(2))
+            .clock(FakeClock::default())
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            client.etymologies("horse"),
+            Err(Error::Status(500))
+        ));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn does_not_retry_posts() {
+        let server = TestServer::start(vec![Reply::status(503), Reply::status(503)]);
+        let client = super::Client::builder("stub_api_key")
+            .base_url(&server.base_url)
+            .auth_token("user-token")
+            .retry_policy(RetryPolicy::new())
+            .clock(FakeClock::default())
+            .build()
+            .unwrap();
+
+        let res = client.add_word_list_words("my-list", &["hearth"]);
+        assert!(matches!(res, Err(Error::Status(503))));
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(server.requests()[0].method, "POST");
+    }
+
+    #[test]
+    fn rate_limits_and_tracks_quota_against_stub() {
+        let quota_reply = |remaining: &str| {
+            Reply::json("[]")
+                .header("X-RateLimit-Limit-Minute", "100")
+                .header("X-RateLimit-Remaining-Minute", remaining)
+        };
+        let server = TestServer::start(vec![quota_reply("99"), quota_reply("98")]);
+        let clock = Arc::new(FakeClock::default());
+        let client = super::Client::builder("stub_api_key")
+            .base_url(&server.base_url)
+            .rate_limit(RateLimit::per_minute(1))
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        assert_eq!(client.quota_status(), None);
+
+        client.etymologies("horse").unwrap();
+        client.clone().etymologies("cart").unwrap();
+
+        // The second call waited for the first's token to come back, even from a clone.
+        assert_eq!(*clock.slept.lock().unwrap(), [Duration::from_secs(60)]);
+        let quota = client.quota_status().unwrap();
+        assert_eq!(quota.minute_limit, Some(100));
+        assert_eq!(quota.minute_remaining, Some(98));
+        assert_eq!(quota.hour_remaining, None);
+    }
+
+    #[test]
+    fn rate_limit_can_fail_fast() {
+        let transport = FixtureTransport::new().with_body("/word.json/horse/etymologies", "[]");
+        let client = super::Client::builder("fixture_api_key")
+            .transport(transport)
+            .rate_limit(RateLimit::per_hour(1).fail_fast())
+            .clock(FakeClock::default())
+            .build()
+            .unwrap();
+
+        client.etymologies("horse").unwrap();
+        assert!(matches!(
+            client.etymologies("horse"),
+            Err(Error::RateLimited(_))
+        ));
+    }
+
+    #[test]
+    fn audio_downloads_dont_count_against_rate_limits() {
+        let transport = FixtureTransport::new()
+            .with_body(
+                "/word.json/horse/audio",
+                r#"[{"id":1,"fileUrl":"https://audio.example/horse.mp3"}]"#,
+            )
+            .with_body("/horse.mp3", "mp3");
+        let client = super::Client::builder("fixture_api_key")
+            .transport(transport)
+            .rate_limit(RateLimit::per_hour(1).fail_fast())
+            .clock(FakeClock::default())
+            .build()
+            .unwrap();
+
+        let files = client.audio("horse", &AudioArgs::new()).unwrap();
+        assert_eq!(client.download_audio(&files[0]).unwrap(), b"mp3");
+        assert_eq!(client.download_audio(&files[0]).unwrap(), b"mp3");
+    }
+
+    fn caching_client(transport: &Arc<FixtureTransport>, clock: &Arc<FakeClock>) -> super::Client {
+        super::Client::builder("fixture_api_key")
+            .transport(transport.clone())
+            .cache(Cache::new(MemoryCache::new(10)))
+            .clock(clock.clone())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn caches_responses_by_endpoint_and_args() {
+        let transport = Arc::new(
+            FixtureTransport::new()
+                .with_body("/word.json/fireplace/definitions", "[]")
+                .with_body("/words.json/randomWord", r#"{ "id": 1, "word": "ingle" }"#),
+        );
+        let client = caching_client(&transport, &Default::default());
+
+        let mut args = DefinitionsArgs::new();
+        client.definitions("fireplace").unwrap();
+        client.definitions("fireplace").unwrap();
+        args.limit = 1;
+        client.definitions_args("fireplace", &args).unwrap();
+        assert_eq!(transport.requests().len(), 2);
+        assert_eq!(
+            client.cache_stats(),
+            Some(CacheStats { hits: 1, misses: 2 })
+        );
+
+        // Random words are never cached, and don't count as misses.
+        client.random_word().unwrap();
+        client.random_word().unwrap();
+        assert_eq!(transport.requests().len(), 4);
+        assert_eq!(client.cache_stats().unwrap().misses, 2);
+    }
+
+    #[test]
+    fn caches_word_of_the_day_until_midnight() {
+        let transport = Arc::new(
+            FixtureTransport::new().with_body("/words.json/wordOfTheDay", r#"{ "word": "ingle" }"#),
+        );
+        let clock = Arc::new(FakeClock::default());
+        let client = caching_client(&transport, &clock);
+
+        clock.sleep(Duration::from_secs(12 * 3_600));
+        client.word_of_the_day(None).unwrap();
+        clock.sleep(Duration::from_secs(11 * 3_600));
+        client.word_of_the_day(None).unwrap();
+        assert_eq!(transport.requests().len(), 1);
+
+        clock.sleep(Duration::from_secs(2 * 3_600));
+        client.word_of_the_day(None).unwrap();
+        assert_eq!(transport.requests().len(), 2);
+    }
 }
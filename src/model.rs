@@ -1,11 +1,92 @@
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::ops::Index;
+
+use crate::{args::RelationshipType, ApiKey};
 
 // Of course, there are a boatload of properties here that I have no clue about.
 // For now, I have skipped deserialization of several of these.
 
 #[derive(Clone, Debug, Deserialize)]
-#[serde(rename = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTokenStatus {
+    pub valid: bool,
+    pub token: Option<ApiKey>,
+    #[serde(default)]
+    pub resets_in_millis: i64,
+    #[serde(default)]
+    pub remaining_calls: i64,
+    #[serde(default)]
+    pub expires_in_millis: i64,
+    #[serde(default)]
+    pub total_requests: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioFile {
+    pub id: i64,
+    pub word: Option<String>,
+    pub file_url: Option<String>,
+    pub duration: Option<f64>,
+    pub audio_type: Option<String>,
+    pub attribution_text: Option<String>,
+    pub attribution_url: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub comment_count: i32,
+    #[serde(default)]
+    pub vote_count: i32,
+    pub vote_average: Option<f32>,
+    pub vote_weighted_average: Option<f32>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationToken {
+    pub token: String,
+    pub user_id: Option<i64>,
+    pub user_signature: Option<String>,
+}
+
+impl fmt::Debug for AuthenticationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthenticationToken")
+            .field("token", &"<auth_token>")
+            .field("user_id", &self.user_id)
+            .field("user_signature", &self.user_signature)
+            .finish()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Bigram {
+    pub gram1: String,
+    pub gram2: String,
+    pub count: i64,
+    pub mi: f64,
+    pub wlmi: f64,
+}
+
+impl Bigram {
+    /// Orders bigrams from highest to lowest mutual information, for use with `sort_by`.
+    pub fn by_mi(a: &Bigram, b: &Bigram) -> Ordering {
+        b.mi.total_cmp(&a.mi)
+    }
+
+    /// Orders bigrams from highest to lowest weighted mutual information, for use with
+    /// `sort_by`.
+    pub fn by_wlmi(a: &Bigram, b: &Bigram) -> Ordering {
+        b.wlmi.total_cmp(&a.wlmi)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Definition {
     pub part_of_speech: Option<String>,
     pub attribution_url: Option<String>,
@@ -14,31 +95,272 @@ pub struct Definition {
     pub text: Option<String>,
 
     // FIXME: same comment as with citations. >.< I mean, I have no idea what labels exist.
+    #[serde(default = "Vec::new")]
     pub labels: Vec<HashMap<String, String>>,
 
     // FIXME: I have no freaking clue why this isn't just a list of strings, but apparently this
     // property is implemented as a list of maps. Maps of what? No idea. Citations are rare enough
     // that I don't have a lot to go on, and the docs are unclear.
+    #[serde(default = "Vec::new")]
     pub citations: Vec<HashMap<String, String>>,
 
     pub word: String,
     #[serde(default = "Vec::new")]
-    pub related_words: Vec<String>,
+    pub related_words: Vec<Related>,
     #[serde(default = "Vec::new")]
-    pub example_uses: Vec<String>,
+    pub example_uses: Vec<ExampleUse>,
     #[serde(default = "Vec::new")]
-    pub notes: Vec<String>,
+    pub notes: Vec<Note>,
     pub wordnik_url: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
-#[serde(rename = "camelCase", transparent)]
+#[serde(rename_all = "camelCase")]
+pub struct DefinitionSearchResults {
+    #[serde(default = "Vec::new")]
+    pub results: Vec<Definition>,
+    #[serde(default)]
+    pub total_results: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(transparent)]
 pub struct Etymology {
     pub etymology: String,
 }
 
 #[derive(Clone, Debug, Deserialize)]
-#[serde(rename = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub struct Example {
+    pub id: Option<i64>,
+    pub example_id: Option<i64>,
+    pub document_id: Option<i64>,
+    pub word: Option<String>,
+    pub text: String,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub year: Option<i32>,
+    pub rating: Option<f32>,
+    pub provider: Option<ContentProvider>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ContentProvider {
+    pub id: Option<i64>,
+    pub name: Option<String>,
+}
+
+/// A short usage example attached to a definition, as opposed to an `Example` from the
+/// examples endpoint.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExampleUse {
+    pub text: String,
+    #[serde(default)]
+    pub position: i32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExampleSearchResults {
+    #[serde(default = "Vec::new")]
+    pub examples: Vec<Example>,
+    #[serde(default = "Vec::new")]
+    pub facets: Vec<Facet>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Facet {
+    pub name: Option<String>,
+    #[serde(default = "Vec::new")]
+    pub facet_values: Vec<FacetValue>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct FacetValue {
+    pub count: i64,
+    pub value: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Frequency {
+    // The api has been known to send years as strings.
+    #[serde(deserialize_with = "int_or_string")]
+    pub year: i32,
+    pub count: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequencySummary {
+    pub word: Option<String>,
+    #[serde(default)]
+    pub total_count: i64,
+    #[serde(default)]
+    pub unknown_year_count: i64,
+    pub frequency_string: Option<String>,
+    #[serde(default = "Vec::new")]
+    pub frequency: Vec<Frequency>,
+}
+
+impl FrequencySummary {
+    /// The year with the highest count. Ties go to the earliest year.
+    pub fn peak_year(&self) -> Option<&Frequency> {
+        self.frequency
+            .iter()
+            .fold(None, |peak: Option<&Frequency>, item| match peak {
+                Some(peak) if peak.count > item.count => Some(peak),
+                Some(peak) if peak.count == item.count && peak.year <= item.year => Some(peak),
+                _ => Some(item),
+            })
+    }
+
+    /// Counts summed by decade, keyed by the first year of each decade (e.g. 1990).
+    pub fn by_decade(&self) -> BTreeMap<i32, i64> {
+        let mut decades = BTreeMap::new();
+        for item in &self.frequency {
+            *decades.entry(item.year.div_euclid(10) * 10).or_insert(0) += item.count;
+        }
+        decades
+    }
+
+    /// The slope of a least-squares fit of each year's count divided by the mean count.
+    ///
+    /// Because counts are scaled by the word's own average, this is comparable between common
+    /// and rare words: 0.01 means usage grows by about one percent of its average per year, and
+    /// a negative value means the word is falling out of use. Returns `None` when there are
+    /// fewer than two years or no usage at all.
+    pub fn normalized_trend(&self) -> Option<f64> {
+        let n = self.frequency.len() as f64;
+        if self.frequency.len() < 2 {
+            return None;
+        }
+
+        let mean_count = self.frequency.iter().map(|f| f.count as f64).sum::<f64>() / n;
+        if mean_count == 0.0 {
+            return None;
+        }
+
+        let mean_year = self
+            .frequency
+            .iter()
+            .map(|f| f64::from(f.year))
+            .sum::<f64>()
+            / n;
+        let (covariance, variance) =
+            self.frequency
+                .iter()
+                .fold((0.0, 0.0), |(covariance, variance), item| {
+                    let dx = f64::from(item.year) - mean_year;
+                    let dy = item.count as f64 / mean_count - 1.0;
+                    (covariance + dx * dy, variance + dx * dx)
+                });
+
+        if variance == 0.0 {
+            None
+        } else {
+            Some(covariance / variance)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(transparent)]
+pub struct Hyphenation {
+    pub syllables: Vec<Syllable>,
+}
+
+impl Hyphenation {
+    /// Joins the syllables with the given separator, e.g. "hy-phen-a-tion" for "-".
+    pub fn join(&self, separator: &str) -> String {
+        let mut syllables = self.syllables.iter();
+        let mut buf = match syllables.next() {
+            Some(first) => first.text.clone(),
+            None => return String::new(),
+        };
+
+        for syllable in syllables {
+            buf += separator;
+            buf += &syllable.text;
+        }
+
+        buf
+    }
+
+    /// Joins the syllables with soft hyphens (U+00AD), which are invisible unless the word is
+    /// broken across lines.
+    pub fn soft_hyphenated(&self) -> String {
+        self.join("\u{ad}")
+    }
+
+    /// Syllables carrying primary stress.
+    pub fn stressed(&self) -> impl Iterator<Item = &Syllable> {
+        self.syllables
+            .iter()
+            .filter(|syllable| syllable.is_stressed())
+    }
+}
+
+/// Renders the syllables separated by middle dots, e.g. "hy·phen·a·tion".
+impl fmt::Display for Hyphenation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.join("\u{b7}"))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScrabbleScore {
+    pub value: i32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimpleDefinition {
+    pub text: String,
+    pub source: Option<String>,
+    pub note: Option<String>,
+    pub part_of_speech: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SimpleExample {
+    pub id: Option<i64>,
+    pub title: Option<String>,
+    pub text: String,
+    pub url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Syllable {
+    pub text: String,
+    pub seq: i32,
+    // Either "stress" or "secondary stress" when present.
+    #[serde(rename = "type")]
+    pub syllable_type: Option<String>,
+}
+
+impl Syllable {
+    pub fn is_stressed(&self) -> bool {
+        self.syllable_type.as_deref() == Some("stress")
+    }
+
+    pub fn is_secondary_stressed(&self) -> bool {
+        self.syllable_type.as_deref() == Some("secondary stress")
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    pub note_type: Option<String>,
+    #[serde(default = "Vec::new")]
+    pub applies_to: Vec<String>,
+    pub value: Option<String>,
+    #[serde(default)]
+    pub pos: i32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RandomWord {
     pub canonical_form: Option<String>,
     pub id: i32,
@@ -48,3 +370,344 @@ pub struct RandomWord {
     pub vulgar: Option<String>,
     pub word: String,
 }
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Related {
+    pub relationship_type: String,
+    #[serde(default = "Vec::new")]
+    pub words: Vec<String>,
+}
+
+/// Related words grouped by relationship type.
+///
+/// The api sends these as a flat list of `{ relationshipType, words }` objects; they're grouped
+/// here so that callers can ask for e.g. `related.synonyms()` or `related[RelationshipType::Rhyme]`
+/// directly. Relationship types this crate doesn't know about are filed under
+/// `RelationshipType::Unknown`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(from = "Vec<Related>")]
+pub struct RelatedWords {
+    groups: HashMap<RelationshipType, Vec<String>>,
+}
+
+impl RelatedWords {
+    pub fn get(&self, relationship_type: RelationshipType) -> &[String] {
+        self.groups
+            .get(&relationship_type)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn synonyms(&self) -> &[String] {
+        self.get(RelationshipType::Synonym)
+    }
+
+    pub fn antonyms(&self) -> &[String] {
+        self.get(RelationshipType::Antonym)
+    }
+
+    pub fn hypernyms(&self) -> &[String] {
+        self.get(RelationshipType::Hypernym)
+    }
+
+    pub fn hyponyms(&self) -> &[String] {
+        self.get(RelationshipType::Hyponym)
+    }
+
+    pub fn rhymes(&self) -> &[String] {
+        self.get(RelationshipType::Rhyme)
+    }
+
+    pub fn same_context(&self) -> &[String] {
+        self.get(RelationshipType::SameContext)
+    }
+
+    /// Relationship types with at least one word, in no particular order.
+    pub fn relationship_types(&self) -> impl Iterator<Item = RelationshipType> + '_ {
+        self.groups.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (RelationshipType, &[String])> {
+        self.groups
+            .iter()
+            .map(|(&relationship_type, words)| (relationship_type, words.as_slice()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+impl From<Vec<Related>> for RelatedWords {
+    fn from(related: Vec<Related>) -> Self {
+        let mut groups: HashMap<_, Vec<_>> = HashMap::new();
+        for Related {
+            relationship_type,
+            words,
+        } in related
+        {
+            groups
+                .entry(RelationshipType::from_str_lossy(&relationship_type))
+                .or_default()
+                .extend(words);
+        }
+        Self { groups }
+    }
+}
+
+impl Index<RelationshipType> for RelatedWords {
+    type Output = [String];
+
+    fn index(&self, relationship_type: RelationshipType) -> &Self::Output {
+        self.get(relationship_type)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextPron {
+    pub id: Option<i64>,
+    pub raw: String,
+    // The format of `raw`, e.g. "ahd-5" or "IPA".
+    pub raw_type: Option<String>,
+    pub seq: i32,
+    pub attribution_text: Option<String>,
+    pub attribution_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordOfTheDay {
+    pub id: Option<String>,
+    pub word: String,
+    // e.g. "2021-09-25T03:00:00.000Z"; parse with `Date::from_str` if you only need the day.
+    pub publish_date: Option<String>,
+    pub note: Option<String>,
+    pub content_provider: Option<ContentProvider>,
+    #[serde(default = "Vec::new")]
+    pub definitions: Vec<SimpleDefinition>,
+    #[serde(default = "Vec::new")]
+    pub examples: Vec<SimpleExample>,
+}
+
+#[cfg(feature = "deprecated-endpoints")]
+#[derive(Clone, Debug, Deserialize)]
+pub struct WordSearchResult {
+    pub word: String,
+    #[serde(default)]
+    pub count: i64,
+    pub lexicality: Option<f64>,
+}
+
+#[cfg(feature = "deprecated-endpoints")]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordSearchResults {
+    #[serde(default = "Vec::new")]
+    pub search_results: Vec<WordSearchResult>,
+    #[serde(default)]
+    pub total_results: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordList {
+    pub id: i64,
+    pub permalink: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub username: Option<String>,
+    pub user_id: Option<i64>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub last_activity_at: Option<String>,
+    #[serde(default)]
+    pub number_words_in_list: i64,
+    // "PUBLIC" or "PRIVATE"
+    #[serde(rename = "type")]
+    pub list_type: Option<String>,
+}
+
+/// The parts of a word list that can be set when creating or updating it.
+#[derive(Clone, Debug, Serialize)]
+pub struct WordListMetadata {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub list_type: WordListType,
+}
+
+impl WordListMetadata {
+    pub fn new(name: impl Into<String>, list_type: WordListType) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            list_type,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum WordListType {
+    Public,
+    Private,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordListWord {
+    pub id: Option<i64>,
+    pub word: String,
+    pub username: Option<String>,
+    pub user_id: Option<i64>,
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub number_comments_on_word: i64,
+    #[serde(default)]
+    pub number_lists: i64,
+}
+
+fn int_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum IntOrString {
+        Int(i32),
+        String(String),
+    }
+
+    match IntOrString::deserialize(deserializer)? {
+        IntOrString::Int(n) => Ok(n),
+        IntOrString::String(s) => s.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bigram, Definition, Etymology, FrequencySummary, Hyphenation, RelatedWords};
+    use crate::args::RelationshipType;
+
+    fn summary() -> FrequencySummary {
+        serde_json::from_str(
+            r#"{
+                "totalCount": 21,
+                "unknownYearCount": 0,
+                "frequencyString": "",
+                "frequency": [
+                    { "year": "1998", "count": 2 },
+                    { "year": 1999, "count": 4 },
+                    { "year": "2000", "count": 7 },
+                    { "year": 2001, "count": 8 }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn peak_year() {
+        assert_eq!(summary().peak_year().unwrap().year, 2001);
+    }
+
+    #[test]
+    fn by_decade() {
+        let decades = summary().by_decade();
+        assert_eq!(decades[&1990], 6);
+        assert_eq!(decades[&2000], 15);
+    }
+
+    #[test]
+    fn normalized_trend() {
+        let trend = summary().normalized_trend().unwrap();
+        assert!((trend - 0.4).abs() < 1e-9, "{}", trend);
+    }
+
+    fn hyphenation() -> Hyphenation {
+        serde_json::from_str(
+            r#"[
+                { "text": "hy", "seq": 0, "type": "secondary stress" },
+                { "text": "phen", "seq": 1 },
+                { "text": "a", "seq": 2, "type": "stress" },
+                { "text": "tion", "seq": 3 }
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn hyphenation_rendering() {
+        let hyphenation = hyphenation();
+        assert_eq!(hyphenation.to_string(), "hy\u{b7}phen\u{b7}a\u{b7}tion");
+        assert_eq!(
+            hyphenation.soft_hyphenated(),
+            "hy\u{ad}phen\u{ad}a\u{ad}tion"
+        );
+        assert_eq!(hyphenation.join("-"), "hy-phen-a-tion");
+    }
+
+    #[test]
+    fn stressed_syllables() {
+        let hyphenation = hyphenation();
+        let stressed: Vec<_> = hyphenation.stressed().map(|s| s.text.as_str()).collect();
+        assert_eq!(stressed, ["a"]);
+        assert!(hyphenation.syllables[0].is_secondary_stressed());
+    }
+
+    #[test]
+    fn bigram_ordering() {
+        let mut bigrams: Vec<Bigram> = serde_json::from_str(
+            r#"[
+                { "gram1": "stone", "gram2": "fireplace", "count": 12, "mi": 9.5, "wlmi": 14.2 },
+                { "gram1": "fireplace", "gram2": "mantel", "count": 40, "mi": 11.0, "wlmi": 13.1 },
+                { "gram1": "the", "gram2": "fireplace", "count": 900, "mi": 2.1, "wlmi": 15.0 }
+            ]"#,
+        )
+        .unwrap();
+
+        bigrams.sort_by(Bigram::by_mi);
+        assert_eq!(bigrams[0].gram2, "mantel");
+
+        bigrams.sort_by(Bigram::by_wlmi);
+        assert_eq!(bigrams[0].gram1, "the");
+    }
+
+    #[test]
+    fn related_words_are_grouped() {
+        let related: RelatedWords = serde_json::from_str(
+            r#"[
+                { "relationshipType": "synonym", "words": ["hearth"] },
+                { "relationshipType": "rhyme", "words": ["birthplace"] },
+                { "relationshipType": "synonym", "words": ["ingle"] },
+                { "relationshipType": "brand-new-type", "words": ["chimney"] }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(related.synonyms(), ["hearth", "ingle"]);
+        assert_eq!(&related[RelationshipType::Rhyme], ["birthplace"]);
+        assert_eq!(&related[RelationshipType::Unknown], ["chimney"]);
+        assert!(related.antonyms().is_empty());
+    }
+
+    #[test]
+    fn definitions_decode_from_api_response() {
+        let definitions: Vec<Definition> =
+            serde_json::from_str(include_str!("../resource/response.json")).unwrap();
+        assert!(definitions.iter().all(|d| d.word == "fireplace"));
+        assert_eq!(definitions[0].part_of_speech.as_deref(), Some("noun"));
+        assert!(definitions
+            .iter()
+            .flat_map(|d| &d.related_words)
+            .any(|r| r.relationship_type == "synonym"));
+        assert!(definitions.iter().any(|d| !d.example_uses.is_empty()));
+    }
+
+    #[test]
+    fn etymologies_decode_from_api_response() {
+        let etymologies: Vec<Etymology> =
+            serde_json::from_str(include_str!("../resource/etymologies.json")).unwrap();
+        assert!(etymologies[0].etymology.contains("horsion"));
+    }
+}
@@ -0,0 +1,336 @@
+//! Keeping under wordnik's per-minute and per-hour call limits.
+
+use std::{
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use reqwest::header::HeaderMap;
+
+#[cfg(feature = "blocking")]
+use crate::retry::Clock;
+use crate::{error::Error, Result};
+
+static LIMIT_MINUTE: &str = "x-ratelimit-limit-minute";
+static REMAINING_MINUTE: &str = "x-ratelimit-remaining-minute";
+static LIMIT_HOUR: &str = "x-ratelimit-limit-hour";
+static REMAINING_HOUR: &str = "x-ratelimit-remaining-hour";
+
+const MINUTE: Duration = Duration::from_secs(60);
+const HOUR: Duration = Duration::from_secs(3_600);
+
+/// What the api said about the caller's quotas, as of the most recent response that said
+/// anything. See `Client::quota_status`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct QuotaStatus {
+    pub minute_limit: Option<u32>,
+    pub minute_remaining: Option<u32>,
+    pub hour_limit: Option<u32>,
+    pub hour_remaining: Option<u32>,
+    /// When the response carrying these numbers arrived.
+    pub received: SystemTime,
+}
+
+impl QuotaStatus {
+    // `None` if the response didn't have any rate limit headers, e.g. because it came from
+    // somewhere other than the api.
+    pub(crate) fn from_headers(headers: &HeaderMap, received: SystemTime) -> Option<Self> {
+        let read = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse().ok();
+        let status = Self {
+            minute_limit: read(LIMIT_MINUTE),
+            minute_remaining: read(REMAINING_MINUTE),
+            hour_limit: read(LIMIT_HOUR),
+            hour_remaining: read(REMAINING_HOUR),
+            received,
+        };
+
+        let any = status.minute_limit.is_some()
+            || status.minute_remaining.is_some()
+            || status.hour_limit.is_some()
+            || status.hour_remaining.is_some();
+        if any {
+            Some(status)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the api has said that there are no calls left this minute or this hour.
+    pub fn is_exhausted(&self) -> bool {
+        self.minute_remaining == Some(0) || self.hour_remaining == Some(0)
+    }
+}
+
+/// Limits how often a client calls the api, so that it slows down on its own rather than being
+/// told to with a 429.
+///
+/// Each limit is a token bucket: it starts full, every call takes a token, and tokens come back
+/// at a steady rate, so that short bursts are fine as long as the average stays under the limit.
+/// When a client has more than one limit, a call has to fit under all of them.
+///
+/// An `AsyncClient` can't wait without knowing which runtime it's on, so it only takes limits
+/// that fail fast; `ClientBuilder::build_async` fails if given one that doesn't.
+///
+/// ```no_run
+/// # #[cfg(feature = "blocking")]
+/// # fn main() -> wordnik::Result<()> {
+/// use wordnik::rate_limit::RateLimit;
+///
+/// let client = wordnik::Client::builder("my-api-key")
+///     .rate_limit(RateLimit::per_minute(100))
+///     .rate_limit(RateLimit::per_hour(1_000).fail_fast())
+///     .build()?;
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "blocking"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimit {
+    calls: u32,
+    period: Duration,
+    fail_fast: bool,
+}
+
+impl RateLimit {
+    /// `calls` calls every `period`. A limit of zero calls is treated as one.
+    pub fn new(calls: u32, period: Duration) -> Self {
+        Self {
+            calls: calls.max(1),
+            period,
+            fail_fast: false,
+        }
+    }
+
+    pub fn per_minute(calls: u32) -> Self {
+        Self::new(calls, MINUTE)
+    }
+
+    pub fn per_hour(calls: u32) -> Self {
+        Self::new(calls, HOUR)
+    }
+
+    /// Fails with `Error::RateLimited` when the limit is reached, rather than waiting for the
+    /// next call to be allowed.
+    pub fn fail_fast(mut self) -> Self {
+        self.fail_fast = true;
+        self
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn is_fail_fast(&self) -> bool {
+        self.fail_fast
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    limit: RateLimit,
+    tokens: f64,
+    updated: Option<SystemTime>,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: SystemTime) {
+        if let Some(updated) = self.updated {
+            // A clock that goes backwards refills nothing.
+            let elapsed = now.duration_since(updated).unwrap_or_default();
+            let refilled = elapsed.as_secs_f64() / self.limit.period.as_secs_f64()
+                * f64::from(self.limit.calls);
+            self.tokens = (self.tokens + refilled).min(f64::from(self.limit.calls));
+        }
+        self.updated = Some(now);
+    }
+
+    // How long until there's a whole token.
+    fn wait(&self) -> Duration {
+        let missing = (1.0 - self.tokens).max(0.0);
+        self.limit
+            .period
+            .mul_f64(missing / f64::from(self.limit.calls))
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    buckets: Vec<Bucket>,
+    quota: Option<QuotaStatus>,
+}
+
+/// The rate limits and quota of a client and its clones.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    state: Mutex<State>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: Vec<RateLimit>) -> Self {
+        let buckets = limits
+            .into_iter()
+            .map(|limit| Bucket {
+                tokens: f64::from(limit.calls),
+                limit,
+                updated: None,
+            })
+            .collect();
+
+        Self {
+            state: Mutex::new(State {
+                buckets,
+                quota: None,
+            }),
+        }
+    }
+
+    /// Takes a token from every bucket, waiting for them to refill if need be.
+    #[cfg(feature = "blocking")]
+    pub fn acquire(&self, clock: &dyn Clock) -> Result<()> {
+        loop {
+            match self.take(clock.now()) {
+                Ok(()) => return Ok(()),
+                Err((wait, true)) => return Err(Error::RateLimited(wait)),
+                Err((wait, false)) => clock.sleep(wait),
+            }
+        }
+    }
+
+    /// Takes a token from every bucket, or fails if any of them is empty, for clients that
+    /// can't wait.
+    #[cfg(feature = "async")]
+    pub fn try_acquire(&self, now: SystemTime) -> Result<()> {
+        self.take(now).map_err(|(wait, _)| Error::RateLimited(wait))
+    }
+
+    // Takes a token from every bucket if they all have one. Otherwise, how long until they do,
+    // and whether any of the empty ones fails fast.
+    fn take(&self, now: SystemTime) -> Result<(), (Duration, bool)> {
+        let mut state = self.state.lock().unwrap();
+        state
+            .buckets
+            .iter_mut()
+            .for_each(|bucket| bucket.refill(now));
+
+        let empty = state.buckets.iter().filter(|bucket| bucket.tokens < 1.0);
+        match empty.clone().map(Bucket::wait).max() {
+            None => {
+                state
+                    .buckets
+                    .iter_mut()
+                    .for_each(|bucket| bucket.tokens -= 1.0);
+                Ok(())
+            }
+            Some(wait) => Err((wait, empty.clone().any(|bucket| bucket.limit.fail_fast))),
+        }
+    }
+
+    /// Records the quota reported by a response. Where the api says fewer calls are left than a
+    /// bucket of the same period thinks, the bucket takes the api's word for it, since other
+    /// clients may be sharing the api key.
+    pub fn observe(&self, headers: &HeaderMap, now: SystemTime) {
+        let quota = match QuotaStatus::from_headers(headers, now) {
+            Some(quota) => quota,
+            None => return,
+        };
+
+        let mut state = self.state.lock().unwrap();
+        for bucket in &mut state.buckets {
+            let remaining = match bucket.limit.period {
+                MINUTE => quota.minute_remaining,
+                HOUR => quota.hour_remaining,
+                _ => None,
+            };
+            if let Some(remaining) = remaining {
+                bucket.tokens = bucket.tokens.min(f64::from(remaining));
+            }
+        }
+        state.quota = Some(quota);
+    }
+
+    pub fn quota(&self) -> Option<QuotaStatus> {
+        self.state.lock().unwrap().quota
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use super::{RateLimit, RateLimiter};
+    use crate::{error::Error, retry::FakeClock};
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|&(name, value)| (name.parse().unwrap(), HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    #[test]
+    fn waits_for_tokens() {
+        let clock = FakeClock::default();
+        let limiter = RateLimiter::new(vec![RateLimit::per_minute(2)]);
+
+        for _ in 0..3 {
+            limiter.acquire(&clock).unwrap();
+        }
+        // Two calls fit in the bucket; the third waits for half a minute's refill.
+        assert_eq!(*clock.slept.lock().unwrap(), [Duration::from_secs(30)]);
+    }
+
+    #[test]
+    fn fails_fast_when_asked_to() {
+        let clock = FakeClock::default();
+        let limiter = RateLimiter::new(vec![RateLimit::per_minute(1).fail_fast()]);
+
+        limiter.acquire(&clock).unwrap();
+        assert!(matches!(
+            limiter.acquire(&clock),
+            Err(Error::RateLimited(wait)) if wait == Duration::from_secs(60)
+        ));
+        assert!(clock.slept.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn tracks_quota_from_headers() {
+        let clock = FakeClock::default();
+        let limiter = RateLimiter::new(vec![RateLimit::per_minute(100)]);
+        assert_eq!(limiter.quota(), None);
+
+        limiter.observe(&headers(&[]), UNIX_EPOCH);
+        assert_eq!(limiter.quota(), None);
+
+        limiter.observe(
+            &headers(&[
+                ("x-ratelimit-limit-minute", "100"),
+                ("x-ratelimit-remaining-minute", "0"),
+                ("x-ratelimit-limit-hour", "1000"),
+                ("x-ratelimit-remaining-hour", "950"),
+            ]),
+            UNIX_EPOCH,
+        );
+        let quota = limiter.quota().unwrap();
+        assert_eq!(quota.minute_remaining, Some(0));
+        assert_eq!(quota.hour_limit, Some(1000));
+        assert!(quota.is_exhausted());
+
+        // The api says the minute's calls are gone, so the bucket waits for a refill.
+        limiter.acquire(&clock).unwrap();
+        assert_eq!(*clock.slept.lock().unwrap(), [Duration::from_millis(600)]);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn try_acquire_never_waits() {
+        let limiter = RateLimiter::new(vec![RateLimit::per_minute(1)]);
+        limiter.try_acquire(UNIX_EPOCH).unwrap();
+        assert!(matches!(
+            limiter.try_acquire(UNIX_EPOCH),
+            Err(Error::RateLimited(wait)) if wait == Duration::from_secs(60)
+        ));
+        limiter
+            .try_acquire(UNIX_EPOCH + Duration::from_secs(60))
+            .unwrap();
+    }
+}
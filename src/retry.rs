@@ -0,0 +1,367 @@
+//! Retrying requests that fail for reasons that might go away on their own.
+
+use std::{
+    collections::hash_map::RandomState,
+    convert::TryFrom,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    sync::Arc,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{header::RETRY_AFTER, Method};
+
+use crate::{date::Date, error::Error, transport::HttpResponse, Result};
+
+/// Tells the time and waits, so that code which does either can be tested without doing so for
+/// real.
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+impl<T: Clock + ?Sized> Clock for Arc<T> {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        (**self).sleep(duration)
+    }
+}
+
+/// The real clock.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// Keeps track of time without taking any.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct FakeClock {
+    pub slept: std::sync::Mutex<Vec<Duration>>,
+}
+
+#[cfg(test)]
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        UNIX_EPOCH + self.slept.lock().unwrap().iter().sum::<Duration>()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.slept.lock().unwrap().push(duration);
+    }
+}
+
+/// Decides whether, and how long after, a failed request is tried again.
+///
+/// Retries back off exponentially from `base_delay`, up to `max_delay`, with up to `jitter` of
+/// each delay taken off at random so that clients which failed together don't retry together.
+/// Where the api says how long to wait with a `Retry-After` header, that's used instead; if it's
+/// longer than `max_delay`, the request isn't retried at all.
+///
+/// Besides the retryable statuses, requests are retried when they fail to connect or time out.
+/// Only idempotent requests are retried by default: a `POST` that timed out may still have
+/// created a word list, and sending it again would create another. Clients don't retry unless
+/// they're given a policy:
+///
+/// ```no_run
+/// # #[cfg(feature = "blocking")]
+/// # fn main() -> wordnik::Result<()> {
+/// use std::time::Duration;
+/// use wordnik::retry::RetryPolicy;
+///
+/// let client = wordnik::Client::builder("my-api-key")
+///     .retry_policy(RetryPolicy::new().max_attempts(5).base_delay(Duration::from_secs(1)))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "blocking"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+    retryable_statuses: Vec<u16>,
+    retryable_methods: Vec<Method>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: Self::DEFAULT_MAX_ATTEMPTS,
+            base_delay: Self::DEFAULT_BASE_DELAY,
+            max_delay: Self::DEFAULT_MAX_DELAY,
+            jitter: Self::DEFAULT_JITTER,
+            retryable_statuses: Self::DEFAULT_RETRYABLE_STATUSES.to_vec(),
+            retryable_methods: Self::DEFAULT_RETRYABLE_METHODS.to_vec(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+    pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(500);
+    pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+    pub const DEFAULT_JITTER: f64 = 0.5;
+    pub const DEFAULT_RETRYABLE_STATUSES: &'static [u16] = &[429, 500, 502, 503, 504];
+    pub const DEFAULT_RETRYABLE_METHODS: &'static [Method] =
+        &[Method::GET, Method::PUT, Method::DELETE];
+
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Never retries; what clients do if they aren't given a policy.
+    pub fn none() -> Self {
+        Self::new().max_attempts(1)
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn retries(&self) -> bool {
+        self.max_attempts > 1
+    }
+
+    /// How many times to send a request, including the first. Zero is treated as one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The delay before the first retry, doubling for each retry after that.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// The most that can be taken off each delay at random, as a fraction between 0 (none) and
+    /// 1 (all of it).
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn retryable_statuses(mut self, statuses: impl Into<Vec<u16>>) -> Self {
+        self.retryable_statuses = statuses.into();
+        self
+    }
+
+    /// The methods whose requests may be retried. Only add `POST` if resending one can't do
+    /// anything twice.
+    pub fn retryable_methods(mut self, methods: impl Into<Vec<Method>>) -> Self {
+        self.retryable_methods = methods.into();
+        self
+    }
+
+    /// How long to wait before sending a `method` request again, after attempt number `attempt`
+    /// (counting from 1) ended with `result`; `None` if it shouldn't be sent again.
+    pub(crate) fn delay(
+        &self,
+        attempt: u32,
+        method: &Method,
+        result: &Result<HttpResponse>,
+        now: SystemTime,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.retryable_methods.contains(method) {
+            return None;
+        }
+
+        match result {
+            Ok(response) if self.retryable_statuses.contains(&response.status) => {
+                match retry_after(response, now) {
+                    Some(delay) if delay > self.max_delay => None,
+                    Some(delay) => Some(delay),
+                    None => Some(self.backoff(attempt)),
+                }
+            }
+            Err(Error::Network(e)) if e.is_connect() || e.is_timeout() => {
+                Some(self.backoff(attempt))
+            }
+            _ => None,
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt - 1);
+        let delay = self
+            .base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+        delay.mul_f64(1.0 - self.jitter * random_fraction())
+    }
+}
+
+// Uniform in [0, 1). Jitter doesn't need a good random number generator, just a different number
+// each time, which std's randomly keyed hasher will do for us.
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    bits as f64 / (1u64 << 53) as f64
+}
+
+// Retry-After is either a number of seconds or an http date.
+fn retry_after(response: &HttpResponse, now: SystemTime) -> Option<Duration> {
+    let value = response.headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => {
+            let date = parse_http_date(value)?;
+            Some(date.duration_since(now).unwrap_or_default())
+        }
+    }
+}
+
+// Only the preferred format, e.g. "Sun, 06 Nov 1994 08:49:37 GMT", which is all anybody sends.
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    static MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let mut parts = value.split_whitespace().skip(1);
+    let day = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|&m| m == month)? as u32 + 1;
+    let year = parts.next()?.parse().ok()?;
+    let mut time = parts
+        .next()?
+        .split(':')
+        .map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (time.next()??, time.next()??, time.next()??);
+    if parts.next()? != "GMT" {
+        return None;
+    }
+
+    let days = u64::try_from(Date::new(year, month, day)?.to_days()).ok()?;
+    let seconds = days * 86_400 + hours * 3_600 + minutes * 60 + seconds;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use reqwest::{
+        header::{HeaderValue, RETRY_AFTER},
+        Method,
+    };
+
+    use super::RetryPolicy;
+    use crate::transport::HttpResponse;
+
+    fn response(status: u16, retry_after: Option<&'static str>) -> crate::Result<HttpResponse> {
+        let mut response = HttpResponse::new(status, Vec::new());
+        if let Some(retry_after) = retry_after {
+            response
+                .headers
+                .insert(RETRY_AFTER, HeaderValue::from_static(retry_after));
+        }
+        Ok(response)
+    }
+
+    #[test]
+    fn backs_off_exponentially() {
+        let policy = RetryPolicy::new()
+            .max_attempts(5)
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .jitter(0.0);
+        let delays: Vec<_> = (1..=5)
+            .map(|attempt| policy.delay(attempt, &Method::GET, &response(503, None), UNIX_EPOCH))
+            .collect();
+        assert_eq!(
+            delays,
+            [
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(5)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn jitter_only_shortens_delays() {
+        let policy = RetryPolicy::new().base_delay(Duration::from_secs(1));
+        for _ in 0..100 {
+            let delay = policy
+                .delay(1, &Method::GET, &response(500, None), UNIX_EPOCH)
+                .unwrap();
+            assert!(delay > Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn only_retries_retryable_statuses() {
+        let policy = RetryPolicy::new().retryable_statuses(vec![503]);
+        assert!(policy
+            .delay(1, &Method::GET, &response(503, None), UNIX_EPOCH)
+            .is_some());
+        assert!(policy
+            .delay(1, &Method::GET, &response(500, None), UNIX_EPOCH)
+            .is_none());
+        assert!(policy
+            .delay(1, &Method::GET, &response(200, None), UNIX_EPOCH)
+            .is_none());
+        assert!(RetryPolicy::none()
+            .delay(1, &Method::GET, &response(503, None), UNIX_EPOCH)
+            .is_none());
+    }
+
+    #[test]
+    fn only_retries_idempotent_methods() {
+        let policy = RetryPolicy::new();
+        assert!(policy
+            .delay(1, &Method::DELETE, &response(503, None), UNIX_EPOCH)
+            .is_some());
+        assert!(policy
+            .delay(1, &Method::POST, &response(503, None), UNIX_EPOCH)
+            .is_none());
+
+        let policy = policy.retryable_methods(vec![Method::POST]);
+        assert!(policy
+            .delay(1, &Method::POST, &response(503, None), UNIX_EPOCH)
+            .is_some());
+    }
+
+    #[test]
+    fn honors_retry_after() {
+        let policy = RetryPolicy::new();
+        let now = UNIX_EPOCH + Duration::from_secs(784_111_767);
+        assert_eq!(
+            policy.delay(1, &Method::GET, &response(429, Some("7")), now),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(
+            policy.delay(
+                1,
+                &Method::GET,
+                &response(429, Some("Sun, 06 Nov 1994 08:49:37 GMT")),
+                now
+            ),
+            Some(Duration::from_secs(10))
+        );
+        // Longer than the maximum delay, so not worth waiting for.
+        assert_eq!(
+            policy.delay(1, &Method::GET, &response(429, Some("3600")), now),
+            None
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! Offline scrabble scoring, using the standard English tile values.
+//!
+//! This agrees with `Client::scrabble_score` for ordinary words, so games can score locally and
+//! save the api for validation.
+
+/// Scores a word as the sum of its tile values. Letters are case insensitive; anything that
+/// isn't an ascii letter scores zero.
+pub fn score(word: &str) -> u32 {
+    word.chars().map(tile_value).sum()
+}
+
+/// The value of a single tile.
+pub fn tile_value(c: char) -> u32 {
+    match c.to_ascii_lowercase() {
+        'a' | 'e' | 'i' | 'l' | 'n' | 'o' | 'r' | 's' | 't' | 'u' => 1,
+        'd' | 'g' => 2,
+        'b' | 'c' | 'm' | 'p' => 3,
+        'f' | 'h' | 'v' | 'w' | 'y' => 4,
+        'k' => 5,
+        'j' | 'x' => 8,
+        'q' | 'z' => 10,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn scores_words() {
+        assert_eq!(super::score("fireplace"), 16);
+        assert_eq!(super::score("Horse"), 8);
+        assert_eq!(super::score("quiz"), 22);
+        assert_eq!(super::score("ad hoc"), 11);
+        assert_eq!(super::score(""), 0);
+    }
+}
@@ -0,0 +1,212 @@
+//! How requests get from a `Client` to the api and back.
+//!
+//! `Client` hands every request to a `Transport`. By default that's a `ReqwestTransport`, but
+//! anything implementing the trait will do; in particular, `FixtureTransport` answers from canned
+//! responses, so that code built on this crate can be tested without an api key or a network
+//! connection.
+
+#[cfg(feature = "blocking")]
+use std::{fmt, fs, io, path::Path, sync::Arc, sync::Mutex};
+
+#[cfg(feature = "blocking")]
+use percent_encoding::percent_decode_str;
+use reqwest::{header::HeaderMap, Method};
+
+#[cfg(feature = "blocking")]
+use crate::Result;
+
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    pub method: Method,
+    /// The full url, including the query string.
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    /// The path part of the url, without the scheme, host or query string. This is still
+    /// percent-encoded.
+    pub fn path(&self) -> &str {
+        let url = match self.url.find("://") {
+            Some(idx) => &self.url[idx + 3..],
+            None => &self.url,
+        };
+        let url = url.find('/').map_or("", |idx| &url[idx..]);
+        url.split('?').next().unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Sends a request and reads the whole of the response.
+///
+/// Error statuses aren't errors at this level: return them as responses, and the client will
+/// take care of the rest. `Err` is for when there's no response at all.
+#[cfg(feature = "blocking")]
+pub trait Transport: fmt::Debug + Send + Sync {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+// Lets callers keep a handle on a transport after giving it to a client, e.g. to look at the
+// requests a `FixtureTransport` has seen.
+#[cfg(feature = "blocking")]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        (**self).send(request)
+    }
+}
+
+/// The default transport, backed by a blocking reqwest client.
+#[cfg(feature = "blocking")]
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::blocking::Client,
+}
+
+#[cfg(feature = "blocking")]
+impl ReqwestTransport {
+    pub fn new(client: reqwest::blocking::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self
+            .client
+            .request(request.method, &request.url)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send()?;
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            headers: response.headers().clone(),
+            body: response.bytes()?.to_vec(),
+        })
+    }
+}
+
+/// Answers requests from canned responses rather than the network.
+///
+/// Fixtures are matched against the end of the decoded request path, so that
+/// `/word.json/horse/etymologies` answers for any base url, and `/word.json/ad hoc/definitions`
+/// answers for "ad hoc". Query strings are ignored. Where more than one fixture matches, the
+/// longest wins. Requests that match nothing get an empty 404, like they would from the api.
+///
+/// ```
+/// # #[cfg(feature = "blocking")]
+/// # fn main() -> wordnik::Result<()> {
+/// use wordnik::{transport::FixtureTransport, Client};
+///
+/// let transport = FixtureTransport::new().with_body(
+///     "/word.json/horse/etymologies",
+///     r#"["<ety>[AS. <ets>horsion</ets>.]</ety>"]"#,
+/// );
+/// let client = Client::builder("any-api-key").transport(transport).build()?;
+///
+/// assert_eq!(client.etymologies("horse")?.len(), 1);
+/// assert!(client.etymologies("cart").is_err());
+/// # Ok(())
+/// # }
+/// # #[cfg(not(feature = "blocking"))]
+/// # fn main() {}
+/// ```
+#[cfg(feature = "blocking")]
+#[derive(Debug, Default)]
+pub struct FixtureTransport {
+    fixtures: Vec<(String, HttpResponse)>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+#[cfg(feature = "blocking")]
+impl FixtureTransport {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Answers requests for `path` with `body` and a 200.
+    pub fn with_body(self, path: impl Into<String>, body: impl Into<Vec<u8>>) -> Self {
+        self.with_response(path, HttpResponse::new(200, body))
+    }
+
+    /// Answers requests for `path` with the contents of a file, e.g. a response saved from the
+    /// real api.
+    pub fn with_file(self, path: impl Into<String>, file: impl AsRef<Path>) -> io::Result<Self> {
+        let body = fs::read(file)?;
+        Ok(self.with_body(path, body))
+    }
+
+    pub fn with_response(mut self, path: impl Into<String>, response: HttpResponse) -> Self {
+        self.fixtures.push((path.into(), response));
+        self
+    }
+
+    /// Every request sent through this transport so far, oldest first.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Transport for FixtureTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let request_path = percent_decode_str(request.path()).decode_utf8_lossy();
+        let response = self
+            .fixtures
+            .iter()
+            .filter(|(path, _)| request_path.ends_with(path.as_str()))
+            .max_by_key(|(path, _)| path.len())
+            .map(|(_, response)| response.clone())
+            .unwrap_or_else(|| HttpResponse::new(404, Vec::new()));
+
+        self.requests.lock().unwrap().push(request);
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{header::HeaderMap, Method};
+
+    use super::HttpRequest;
+
+    fn request(url: &str) -> HttpRequest {
+        HttpRequest {
+            method: Method::GET,
+            url: url.into(),
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn path_excludes_host_and_query() {
+        let url = "https://api.wordnik.com/v4/word.json/horse/etymologies?api_key=k";
+        assert_eq!(request(url).path(), "/v4/word.json/horse/etymologies");
+        assert_eq!(request("http://localhost:8080").path(), "");
+    }
+}